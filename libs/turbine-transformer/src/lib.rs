@@ -14,7 +14,7 @@ use alloc::collections::{BTreeMap, BTreeSet};
 
 use petgraph::{graph::NodeIndex, Graph};
 use turbine::{
-    entity::{Entity, EntityId, LinkData},
+    entity::{Entity, EntityId, EntityTypeIds, LinkData},
     VersionedUrl, VersionedUrlRef,
 };
 
@@ -92,7 +92,7 @@ impl<'a> View<'a> {
             this.lookup_index
                 .insert(entity.metadata.record_id.entity_id, index);
 
-            if let Some(link_data) = entity.link_data {
+            if let Some(link_data) = &entity.link_data {
                 let lhs = this.get_or_create(link_data.left_entity_id);
                 let rhs = this.get_or_create(link_data.right_entity_id);
 
@@ -118,19 +118,19 @@ impl<'a> View<'a> {
     }
 
     #[must_use]
-    fn entity_type(&self, id: EntityId) -> Option<VersionedUrlRef> {
+    fn entity_types(&self, id: EntityId) -> Option<&EntityTypeIds> {
         let index = *self.lookup_index.get(&id)?;
         let entity = self.entities.get(index)?;
 
-        Some(VersionedUrlRef::from(&entity.metadata.entity_type_id))
+        Some(&entity.metadata.entity_type_ids)
     }
 
     #[must_use]
-    fn entity_link(&self, id: EntityId) -> Option<LinkData> {
+    fn entity_link(&self, id: EntityId) -> Option<&LinkData> {
         let index = *self.lookup_index.get(&id)?;
         let entity = self.entities.get(index)?;
 
-        entity.link_data
+        entity.link_data.as_ref()
     }
 
     #[must_use]
@@ -3,7 +3,7 @@ use alloc::{
     vec::Vec,
 };
 
-use turbine::{entity::Entity, BaseUrl, BaseUrlRef, TypeUrl};
+use turbine::{entity::Entity, path::TypePath, BaseUrl, BaseUrlRef, TypeUrl};
 
 use crate::value::{Object, Value};
 
@@ -34,6 +34,26 @@ impl<'a> From<usize> for Segment<'a> {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct JsonPath<'a>(Cow<'a, [Segment<'a>]>);
 
+impl JsonPath<'static> {
+    /// Create a path from a typed path, e.g. `Person::path().address().street()`, which has been
+    /// checked against the schema at compile time.
+    #[must_use]
+    pub fn from_type_path(path: impl TypePath) -> Self {
+        let path = path.path();
+
+        let segments = path
+            .iter()
+            .map(|segment| match segment {
+                // field names emitted by the codegen are `'static`, cloning keeps them borrowed
+                turbine::path::Segment::Field(field) => Segment::Field(field.clone()),
+                turbine::path::Segment::Index(index) => Segment::Index(*index),
+            })
+            .collect();
+
+        Self(Cow::Owned(segments))
+    }
+}
+
 impl<'a> JsonPath<'a> {
     #[must_use]
     pub const fn new() -> Self {
@@ -192,7 +192,8 @@ pub enum Condition {
 }
 
 // TODO: in future version, PathOrValue should be typed over the Path, this requires major changes
-//  in the codegen.
+//  in the codegen. Until then, use `JsonPath::from_type_path` to construct a path that has been
+//  checked against the schema.
 pub enum PathOrValue<'a> {
     Path(JsonPath<'a>),
     Value(Value<'a>),
@@ -20,22 +20,22 @@ impl TypeMatch<'_> {
             return true;
         }
 
-        let Some(type_) = view.entity_type(id) else {
+        let Some(entity_type_ids) = view.entity_types(id) else {
             return false;
         };
 
-        if self.types.contains(&type_) {
-            return true;
-        }
+        entity_type_ids.iter().any(|type_| {
+            let type_ = VersionedUrlRef::from(type_);
 
-        let inherits_from = (view.lookup_inherits_from)(type_);
+            if self.types.contains(&type_) {
+                return true;
+            }
 
-        let common = self.inherits_from.intersection(&inherits_from).count();
-        if common > 0 {
-            return true;
-        }
+            let inherits_from = (view.lookup_inherits_from)(type_);
 
-        false
+            let common = self.inherits_from.intersection(&inherits_from).count();
+            common > 0
+        })
     }
 
     #[must_use]
@@ -5,17 +5,18 @@ use std::{
 };
 
 use clap::{Args, ValueEnum, ValueHint};
-use codegen::{AnyTypeRepr, Flavor, Override};
+use codegen::{AnyTypeRepr, Flavor, Override, UnknownProperties};
 use error_stack::{Result, ResultExt};
 use figment::{
     providers::{Env, Format, Toml},
     Figment, Profile,
 };
 use reqwest::blocking::Client;
-use serde_json::{json, Value};
+use serde_json::json;
 use skeletor::Style;
 use thiserror::Error;
 use tracing_subscriber::EnvFilter;
+use turbine::subgraph::{QueryTemporalAxes, Subgraph};
 use url::Url;
 use uuid::Uuid;
 
@@ -148,19 +149,7 @@ fn call_remote(url: &Url, actor_id: Uuid) -> Result<Vec<AnyTypeRepr>, Error> {
           "incoming": 0
         }
       },
-      "temporalAxes": {
-        "pinned": {
-          "axis": "transactionTime",
-          "timestamp": null
-        },
-        "variable": {
-          "axis": "decisionTime",
-          "interval": {
-            "start": null,
-            "end": null
-          }
-        }
-      }
+      "temporalAxes": QueryTemporalAxes::current()
     });
 
     let client = Client::new();
@@ -171,26 +160,15 @@ fn call_remote(url: &Url, actor_id: Uuid) -> Result<Vec<AnyTypeRepr>, Error> {
         .send()
         .change_context(Error::Http)?;
 
-    // Do the same as:
-    // .vertices | .[] | .[] | .inner.schema
-    let response: Value = response.json().change_context(Error::Http)?;
-
-    // TODO: propagate error?!
-    let types = response["vertices"]
-        .as_object()
-        .expect("should conform to format")
-        .values()
-        .flat_map(|value| {
-            value
-                .as_object()
-                .expect("should conform to format")
-                .values()
-        })
-        .map(|value| value["inner"]["schema"].clone())
-        .map(|value| serde_json::from_value::<AnyTypeRepr>(value).expect("should be valid type"))
-        .collect();
+    let subgraph: Subgraph = response.json().change_context(Error::Serde)?;
 
-    Ok(types)
+    subgraph
+        .ontology()
+        .map(|(_, _, vertex)| {
+            serde_json::from_value::<AnyTypeRepr>(vertex.schema.clone())
+                .change_context(Error::Serde)
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -251,6 +229,8 @@ pub(crate) struct Config {
 
     overrides: Vec<Override>,
     flavors: Vec<Flavor>,
+    #[serde(default)]
+    unknown_properties: UnknownProperties,
 
     #[serde(default)]
     force: bool,
@@ -359,6 +339,7 @@ pub(crate) fn execute(lib: Lib) -> Result<(), Error> {
 
         overrides: config.overrides,
         flavors: config.flavors,
+        unknown_properties: config.unknown_properties,
 
         force: config.force,
         timings: config.timings,
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use type_system::url::VersionedUrl;
 
@@ -6,12 +6,14 @@ use crate::analysis::unify::LINK_REF;
 
 pub(crate) struct Facts {
     pub(crate) links: HashSet<VersionedUrl>,
+    pub(crate) ancestors: HashMap<VersionedUrl, BTreeSet<VersionedUrl>>,
 }
 
 impl Facts {
     pub(crate) fn new() -> Self {
         Self {
             links: HashSet::new(),
+            ancestors: HashMap::new(),
         }
     }
 
@@ -19,6 +21,12 @@ impl Facts {
         &self.links
     }
 
+    /// All entity types the entity type inherits from, either directly or indirectly, in a stable
+    /// order and without duplicates.
+    pub(crate) fn ancestors(&self, url: &VersionedUrl) -> impl Iterator<Item = &VersionedUrl> {
+        self.ancestors.get(url).into_iter().flatten()
+    }
+
     pub(crate) fn should_skip(&self, url: &VersionedUrl) -> bool {
         url == LINK_REF.url()
     }
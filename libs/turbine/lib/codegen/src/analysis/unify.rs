@@ -214,6 +214,20 @@ impl UnificationAnalyzer {
             // reverse again so that we can use the graph again
             graph.reverse();
         }
+
+        // the transitive ancestors are simply every node reachable from the entity type, as edges
+        // point from the child to the parent
+        for node in graph.node_indices() {
+            let url = graph.node_weight(node).expect("node not found");
+
+            let ancestors = Dfs::new(&*graph, node)
+                .iter(&*graph)
+                .filter(|&ancestor| ancestor != node)
+                .map(|ancestor| graph.node_weight(ancestor).expect("node not found").clone())
+                .collect();
+
+            self.facts.ancestors.insert(url.clone(), ancestors);
+        }
     }
 
     pub(crate) fn stack(&mut self) -> Result<Vec<VersionedUrl>, AnalysisError> {
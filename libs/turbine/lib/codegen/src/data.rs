@@ -1,8 +1,17 @@
-use proc_macro2::TokenStream;
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::{quote, ToTokens};
+use serde_json::{Number, Value};
+use turbine::types::data::constraint::{compile_pattern, Limit};
 use type_system::{url::VersionedUrl, DataType};
 
-use crate::name::NameResolver;
+use crate::{
+    name::NameResolver,
+    shared::{generate_mod, generate_url},
+    AnyType,
+};
 
 pub(crate) struct Builtin {
     url: &'static str,
@@ -20,7 +29,7 @@ impl ToTokens for Builtin {
 
 const ALLOW_LIST: &[Builtin] = &[
     Builtin {
-        url: "https://blockprotocol.org/@blockprotocol/types/data-type/null/",
+        url: NULL,
         use_: "use turbine::types::data::Null",
     },
     Builtin {
@@ -45,6 +54,17 @@ const ALLOW_LIST: &[Builtin] = &[
     },
 ];
 
+const NULL: &str = "https://blockprotocol.org/@blockprotocol/types/data-type/null/";
+
+/// Returns `true` if the data type is `null` or a custom data type of the JSON type `null`.
+pub(crate) fn is_null(url: &VersionedUrl, resolver: &NameResolver) -> bool {
+    if url.base_url.as_str() == NULL {
+        return true;
+    }
+
+    matches!(resolver.get(url), Some(AnyType::Data(data)) if data.json_type() == "null")
+}
+
 pub(crate) fn find_builtin(url: &VersionedUrl) -> Option<&'static Builtin> {
     ALLOW_LIST
         .iter()
@@ -55,15 +75,597 @@ fn is_allowed(data: &DataType) -> bool {
     find_builtin(data.id()).is_some()
 }
 
-// TODO: when generating and we encounter a custom data-type simply import, they will get a
-//  compile_error either way!
-pub(crate) fn generate(data: &DataType, _: &NameResolver) -> Option<TokenStream> {
+/// Builtin data type used as the representation of a custom data type.
+///
+/// Integers and strings of a known `format` are parsed into their native representation.
+fn base(data: &DataType) -> Option<TokenStream> {
+    let format = data
+        .additional_properties()
+        .get("format")
+        .and_then(Value::as_str);
+
+    let base = match (data.json_type(), format) {
+        ("string", Some("date-time")) => quote!(turbine::types::data::DateTime),
+        ("string", Some("date")) => quote!(turbine::types::data::Date),
+        ("string", Some("time")) => quote!(turbine::types::data::Time),
+        ("string", Some("uri")) => quote!(turbine::types::data::Uri),
+        ("string", Some("email")) => quote!(turbine::types::data::Email),
+        ("string", _) => quote!(turbine::types::data::Text),
+        ("integer", _) => quote!(turbine::types::data::Integer),
+        ("number", _) => quote!(turbine::types::data::Number),
+        ("boolean", _) => quote!(turbine::types::data::Boolean),
+        ("null", _) => quote!(turbine::types::data::Null),
+        ("object", _) => quote!(turbine::types::data::Object),
+        _ => return None,
+    };
+
+    Some(base)
+}
+
+/// Single constraint of a data type, `check` is an expression over `value: &serde_json::Value`,
+/// `check_raw` the same expression over `value: &serde_json::value::RawValue`, both are only
+/// evaluated once the value has been verified to be of the base type.
+struct Constraint {
+    name: &'static str,
+    check: TokenStream,
+    check_raw: TokenStream,
+}
+
+/// Checks of a constraint on the parsed and the raw value.
+struct Check {
+    value: TokenStream,
+    raw: TokenStream,
+}
+
+/// `check` is an expression over `value: &str`, raw strings are only unescaped if needed.
+fn string_check(check: &TokenStream) -> Check {
+    Check {
+        value: quote!(value.as_str().map_or(true, |value| #check)),
+        raw: quote!(turbine::raw::string(value).as_deref().map_or(true, |value| #check)),
+    }
+}
+
+/// `check` is an expression over `value: &serde_json::Number`.
+fn number_check(check: &TokenStream) -> Check {
+    Check {
+        value: quote!(match value {
+            serde_json::Value::Number(value) => #check,
+            _ => true,
+        }),
+        raw: quote!(turbine::raw::number(value).as_ref().map_or(true, |value| #check)),
+    }
+}
+
+/// Numeric limit as [`Limit`], integers are kept exact.
+fn limit(number: &Number) -> Option<TokenStream> {
+    let limit = match Limit::from_number(number)? {
+        Limit::Integer(value) => {
+            let value = Literal::i128_unsuffixed(value);
+
+            quote!(turbine::types::data::constraint::Limit::Integer(#value))
+        }
+        Limit::Float(value) => {
+            let value = Literal::f64_unsuffixed(value);
+
+            quote!(turbine::types::data::constraint::Limit::Float(#value))
+        }
+    };
+
+    Some(limit)
+}
+
+/// Expression, which compares `value: &serde_json::Value` structurally with `expected`, so that
+/// `const` and `enum` do not need to parse the expected value at runtime.
+///
+/// Numbers are compared by value, see `turbine::types::data::constraint::equals`.
+fn equals(value: &TokenStream, expected: &Value) -> TokenStream {
+    match expected {
+        Value::Null => quote!(#value.is_null()),
+        Value::Bool(expected) => quote!(#value.as_bool() == Some(#expected)),
+        Value::String(expected) => quote!(#value.as_str() == Some(#expected)),
+        Value::Number(expected) => limit(expected).map_or_else(
+            || quote!(false),
+            |limit| {
+                quote!(matches!(
+                    #value,
+                    serde_json::Value::Number(number)
+                        if turbine::types::data::constraint::compare(number, #limit)
+                            .is_some_and(core::cmp::Ordering::is_eq)
+                ))
+            },
+        ),
+        Value::Array(expected) => {
+            let length = expected.len();
+            // parenthesized, otherwise the reference would be taken of the method call on the item
+            let items = expected
+                .iter()
+                .enumerate()
+                .map(|(index, expected)| equals(&quote!((&array[#index])), expected));
+
+            quote!(matches!(
+                #value,
+                serde_json::Value::Array(array) if array.len() == #length #(&& #items)*
+            ))
+        }
+        Value::Object(expected) => {
+            let length = expected.len();
+            let (keys, values): (Vec<_>, Vec<_>) = expected
+                .iter()
+                .map(|(key, expected)| (key, equals(&quote!(value), expected)))
+                .unzip();
+
+            quote!(matches!(
+                #value,
+                serde_json::Value::Object(object)
+                    if object.len() == #length
+                        #(&& object.get(#keys).is_some_and(|value| #values))*
+            ))
+        }
+    }
+}
+
+/// Equivalent of [`equals`] for `value: &serde_json::value::RawValue`, only the parts of the value
+/// which are compared are parsed.
+fn equals_raw(value: &TokenStream, expected: &Value) -> TokenStream {
+    match expected {
+        Value::Null => quote!((#value.get() == "null")),
+        Value::Bool(expected) => {
+            let expected = if *expected { "true" } else { "false" };
+
+            quote!((#value.get() == #expected))
+        }
+        Value::String(expected) => {
+            quote!(turbine::raw::string(#value).is_some_and(|value| value == #expected))
+        }
+        Value::Number(expected) => limit(expected).map_or_else(
+            || quote!(false),
+            |limit| {
+                quote!(turbine::raw::number(#value).is_some_and(|number| {
+                    turbine::types::data::constraint::compare(&number, #limit)
+                        .is_some_and(core::cmp::Ordering::is_eq)
+                }))
+            },
+        ),
+        Value::Array(expected) => {
+            let length = expected.len();
+            let items = expected
+                .iter()
+                .enumerate()
+                .map(|(index, expected)| equals_raw(&quote!(array[#index]), expected));
+
+            quote!(turbine::raw::array(#value)
+                .is_some_and(|array| array.len() == #length #(&& #items)*))
+        }
+        Value::Object(expected) => {
+            let length = expected.len();
+            let (keys, values): (Vec<_>, Vec<_>) = expected
+                .iter()
+                .map(|(key, expected)| (key, equals_raw(&quote!(value), expected)))
+                .unzip();
+
+            quote!(turbine::raw::object(#value).is_some_and(|object| {
+                object.properties().len() == #length
+                    #(&& object.get(#keys).is_some_and(|value| #values))*
+            }))
+        }
+    }
+}
+
+/// `None` if the pattern cannot be compiled.
+///
+/// The regex engine does not support every ECMA feature, e.g. lookarounds, such a pattern is not
+/// enforced, like any other constraint which isn't understood.
+fn pattern_check(data: &DataType, pattern: &str) -> Option<Check> {
+    if let Err(error) = compile_pattern(pattern) {
+        tracing::warn!(
+            id = %data.id(),
+            ?error,
+            "`pattern` of data type cannot be compiled and is not enforced"
+        );
+
+        return None;
+    }
+
+    // compiled once on first use, instead of on every validation
+    Some(string_check(&quote!({
+        static PATTERN: turbine::types::data::constraint::Pattern =
+            turbine::types::data::constraint::Pattern::new(#pattern);
+
+        turbine::types::data::constraint::matches_pattern(PATTERN.regex(), value)
+    })))
+}
+
+fn constraints(data: &DataType) -> Vec<Constraint> {
+    let mut constraints = vec![];
+
+    // sorted, as otherwise the output might vary between invocations
+    let properties: BTreeMap<_, _> = data.additional_properties().iter().collect();
+
+    for (key, value) in properties {
+        let (name, check) = match (key.as_str(), value) {
+            ("minLength", Value::Number(length)) => {
+                let Some(length) = length.as_u64() else {
+                    continue;
+                };
+                let length = Literal::u64_unsuffixed(length);
+
+                (
+                    "minLength",
+                    string_check(&quote!((value.chars().count() as u64) >= #length)),
+                )
+            }
+            ("maxLength", Value::Number(length)) => {
+                let Some(length) = length.as_u64() else {
+                    continue;
+                };
+                let length = Literal::u64_unsuffixed(length);
+
+                (
+                    "maxLength",
+                    string_check(&quote!((value.chars().count() as u64) <= #length)),
+                )
+            }
+            ("pattern", Value::String(pattern)) => {
+                let Some(check) = pattern_check(data, pattern) else {
+                    continue;
+                };
+
+                ("pattern", check)
+            }
+            ("format", Value::String(format)) => (
+                "format",
+                string_check(
+                    &quote!(turbine::types::data::constraint::matches_format(#format, value)),
+                ),
+            ),
+            (
+                name @ ("minimum" | "maximum" | "exclusiveMinimum" | "exclusiveMaximum"
+                | "multipleOf"),
+                Value::Number(number),
+            ) => {
+                let Some(limit) = limit(number) else {
+                    continue;
+                };
+
+                let compare = |ordering: TokenStream| {
+                    quote!(
+                        turbine::types::data::constraint::compare(value, #limit)
+                            .is_some_and(core::cmp::Ordering::#ordering)
+                    )
+                };
+
+                let (name, check) = match name {
+                    "minimum" => ("minimum", compare(quote!(is_ge))),
+                    "maximum" => ("maximum", compare(quote!(is_le))),
+                    "exclusiveMinimum" => ("exclusiveMinimum", compare(quote!(is_gt))),
+                    "exclusiveMaximum" => ("exclusiveMaximum", compare(quote!(is_lt))),
+                    _ => (
+                        "multipleOf",
+                        quote!(turbine::types::data::constraint::is_multiple_of(value, #limit)),
+                    ),
+                };
+
+                (name, number_check(&check))
+            }
+            ("const", expected) => ("const", Check {
+                value: equals(&quote!(value), expected),
+                raw: equals_raw(&quote!(value), expected),
+            }),
+            ("enum", Value::Array(expected)) if !expected.is_empty() => {
+                let (expected, expected_raw): (Vec<_>, Vec<_>) = expected
+                    .iter()
+                    .map(|expected| {
+                        (
+                            equals(&quote!(value), expected),
+                            equals_raw(&quote!(value), expected),
+                        )
+                    })
+                    .unzip();
+
+                ("enum", Check {
+                    value: quote!(#(#expected)||*),
+                    raw: quote!(#(#expected_raw)||*),
+                })
+            }
+            // unknown keywords are ignored, the graph is responsible for validation
+            _ => continue,
+        };
+
+        constraints.push(Constraint {
+            name,
+            check: check.value,
+            check_raw: check.raw,
+        });
+    }
+
+    constraints
+}
+
+fn doc(data: &DataType) -> TokenStream {
+    let title = data.title();
+    // mimic `#(...)?`
+    let description = data.description().into_iter();
+
+    quote!(
+        #[doc = #title]
+        #(
+            #[doc = ""]
+            #[doc = #description]
+        )*
+    )
+}
+
+/// Generate a newtype over the builtin data type corresponding to the `type` of the data type,
+/// which validates all constraints of the schema during conversion.
+///
+/// A `pattern` which cannot be compiled is skipped with a warning.
+#[allow(clippy::too_many_lines)]
+pub(crate) fn generate(data: &DataType, resolver: &NameResolver) -> Option<TokenStream> {
     // do not issue errors for built-in types
     if is_allowed(data) {
         return None;
     }
 
-    Some(
-        quote!(compile_error!("custom data types are not yet supported, see [#355](https://github.com/blockprotocol/blockprotocol/pull/355) for the official RFC");),
-    )
+    let Some(base) = base(data) else {
+        let message = format!(
+            "data type `{}` is of type `{}`, which is not supported",
+            data.id(),
+            data.json_type()
+        );
+
+        return Some(quote!(compile_error!(#message);));
+    };
+
+    let location = resolver.location(data.id());
+
+    let name = Ident::new(&location.name.value, Span::call_site());
+    let name_ref = Ident::new(&location.name_ref.value, Span::call_site());
+    let name_mut = Ident::new(&location.name_mut.value, Span::call_site());
+
+    let url = generate_url(data.id());
+    let doc = doc(data);
+
+    let (constraint_names, constraint_checks, constraint_checks_raw): (Vec<_>, Vec<_>, Vec<_>) =
+        constraints(data)
+            .into_iter()
+            .map(
+                |Constraint {
+                     name,
+                     check,
+                     check_raw,
+                 }| (name, check, check_raw),
+            )
+            .multiunzip();
+
+    // without constraints there is nothing to validate beyond the base type
+    let validate_raw = (!constraint_names.is_empty()).then(|| {
+        quote! {
+            fn validate_raw(value: &serde_json::value::RawValue) -> Result<(), GenericDataTypeError> {
+                #(
+                    if !(#constraint_checks_raw) {
+                        return Err(Report::new(GenericDataTypeError::Constraint(#constraint_names)));
+                    }
+                )*
+
+                Ok(())
+            }
+        }
+    });
+    let call_validate_raw = validate_raw.as_ref().map(|_| quote!(validate_raw(value)?;));
+
+    let alias = location.name.alias.as_ref().map(|alias| {
+        let alias = Ident::new(alias, Span::call_site());
+
+        quote!(pub type #alias = #name;)
+    });
+    let alias_ref = location.name_ref.alias.as_ref().map(|alias| {
+        let alias = Ident::new(alias, Span::call_site());
+
+        quote!(pub type #alias<'a> = #name_ref<'a>;)
+    });
+    let alias_mut = location.name_mut.alias.as_ref().map(|alias| {
+        let alias = Ident::new(alias, Span::call_site());
+
+        quote!(pub type #alias<'a> = #name_mut<'a>;)
+    });
+
+    let mod_ = generate_mod(&location.kind, resolver);
+
+    Some(quote! {
+        use core::ops::Deref;
+
+        use serde::Serialize;
+        use turbine::{TypeUrl, TypeTraverse, Type, TypeRef, TypeMut};
+        use turbine::{DataType, DataTypeRef, DataTypeMut};
+        use turbine::types::data::DataTypePath;
+        use turbine::{VersionedUrlRef, GenericDataTypeError};
+        use error_stack::{Result, Report, ResultExt as _};
+
+        fn validate(value: &serde_json::Value) -> Result<(), GenericDataTypeError> {
+            if !<#base as DataType>::is_valid_value(value) {
+                return Err(Report::new(GenericDataTypeError::Base));
+            }
+
+            #(
+                if !(#constraint_checks) {
+                    return Err(Report::new(GenericDataTypeError::Constraint(#constraint_names)));
+                }
+            )*
+
+            Ok(())
+        }
+
+        #validate_raw
+
+        #doc
+        #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+        #[serde(transparent)]
+        pub struct #name(#base);
+
+        impl #name {
+            #[must_use]
+            pub fn into_inner(self) -> #base {
+                self.0
+            }
+        }
+
+        impl TryFrom<#base> for #name {
+            type Error = Report<GenericDataTypeError>;
+
+            /// Validates `value` against the constraints of the data type.
+            fn try_from(value: #base) -> core::result::Result<Self, Self::Error> {
+                let json = serde_json::to_value(&value)
+                    .map_err(|_| Report::new(GenericDataTypeError::Base))?;
+                validate(&json)?;
+
+                Ok(Self(value))
+            }
+        }
+
+        impl Deref for #name {
+            type Target = #base;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl TypeUrl for #name {
+            const ID: VersionedUrlRef<'static> = #url;
+        }
+
+        impl TypeTraverse for #name {
+            type Path = DataTypePath;
+        }
+
+        impl Type for #name {
+            type Mut<'a> = #name_mut<'a> where Self: 'a;
+            type Ref<'a> = #name_ref<'a> where Self: 'a;
+
+            fn as_mut(&mut self) -> Self::Mut<'_> {
+                #name_mut(Type::as_mut(&mut self.0))
+            }
+
+            fn as_ref(&self) -> Self::Ref<'_> {
+                #name_ref(Type::as_ref(&self.0))
+            }
+        }
+
+        impl DataType for #name {
+            type Error = GenericDataTypeError;
+
+            fn try_from_value(value: serde_json::Value) -> Result<Self, Self::Error> {
+                validate(&value)?;
+
+                <#base as DataType>::try_from_value(value)
+                    .change_context(GenericDataTypeError::Base)
+                    .map(Self)
+            }
+
+            fn is_valid_value(value: &serde_json::Value) -> bool {
+                validate(value).is_ok()
+            }
+        }
+
+        #alias
+
+        #doc
+        #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+        #[serde(transparent)]
+        pub struct #name_ref<'a>(<#base as Type>::Ref<'a>);
+
+        impl<'a> Deref for #name_ref<'a> {
+            type Target = <#base as Type>::Ref<'a>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl TypeUrl for #name_ref<'_> {
+            const ID: VersionedUrlRef<'static> = #url;
+        }
+
+        impl TypeTraverse for #name_ref<'_> {
+            type Path = DataTypePath;
+        }
+
+        impl TypeRef for #name_ref<'_> {
+            type Owned = #name;
+
+            fn into_owned(self) -> Self::Owned {
+                #name(self.0.into_owned())
+            }
+        }
+
+        impl<'a> DataTypeRef<'a> for #name_ref<'a> {
+            type Error = GenericDataTypeError;
+
+            fn try_from_value(value: &'a serde_json::Value) -> Result<Self, Self::Error> {
+                validate(value)?;
+
+                <<#base as Type>::Ref<'a> as DataTypeRef<'a>>::try_from_value(value)
+                    .change_context(GenericDataTypeError::Base)
+                    .map(Self)
+            }
+
+            fn try_from_raw(value: &'a serde_json::value::RawValue) -> Result<Self, Self::Error> {
+                let base = <<#base as Type>::Ref<'a> as DataTypeRef<'a>>::try_from_raw(value)
+                    .change_context(GenericDataTypeError::Base)?;
+                #call_validate_raw
+
+                Ok(Self(base))
+            }
+        }
+
+        #alias_ref
+
+        #doc
+        #[doc = ""]
+        #[doc = "The value cannot be modified in place, as that would bypass the constraints of the"]
+        #[doc = "data type, use the setters of the entity to replace it instead."]
+        #[derive(Debug, Serialize)]
+        #[serde(transparent)]
+        pub struct #name_mut<'a>(<#base as Type>::Mut<'a>);
+
+        impl<'a> Deref for #name_mut<'a> {
+            type Target = <#base as Type>::Mut<'a>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl TypeUrl for #name_mut<'_> {
+            const ID: VersionedUrlRef<'static> = #url;
+        }
+
+        impl TypeTraverse for #name_mut<'_> {
+            type Path = DataTypePath;
+        }
+
+        impl TypeMut for #name_mut<'_> {
+            type Owned = #name;
+
+            fn into_owned(self) -> Self::Owned {
+                #name(self.0.into_owned())
+            }
+        }
+
+        impl<'a> DataTypeMut<'a> for #name_mut<'a> {
+            type Error = GenericDataTypeError;
+
+            fn try_from_value(value: &'a mut serde_json::Value) -> Result<Self, Self::Error> {
+                validate(value)?;
+
+                <<#base as Type>::Mut<'a> as DataTypeMut<'a>>::try_from_value(value)
+                    .change_context(GenericDataTypeError::Base)
+                    .map(Self)
+            }
+        }
+
+        #alias_mut
+
+        #mod_
+    })
 }
@@ -1,5 +1,11 @@
+mod builder;
+mod like;
+mod link;
+mod setter;
+mod version;
+
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     ops::Deref,
 };
 
@@ -13,12 +19,14 @@ use type_system::{
 
 use crate::{
     name::{Location, NameResolver, PropertyName},
-    shared,
+    path, shared,
     shared::{
-        determine_import_path, generate_mod, generate_properties_is_valid_value, generate_property,
-        generate_property_object_conversion_body, imports, ConversionFunction, Import, Property,
-        Variant,
+        determine_import_path, generate_extra_type, generate_mod,
+        generate_properties_is_valid_value, generate_property,
+        generate_property_object_conversion_body, generate_unknown_properties_check, imports,
+        ConversionFunction, Import, Property, Source, Variant,
     },
+    UnknownProperties,
 };
 
 const RESERVED: &[&str] = &[
@@ -33,6 +41,8 @@ const RESERVED: &[&str] = &[
     "GenericEntityError",
     "Entity",
     "LinkData",
+    "EntityMetadata",
+    "EntityRecord",
     "Serialize",
     "Properties",
     "PropertiesRef",
@@ -46,6 +56,7 @@ const RESERVED: &[&str] = &[
 struct State {
     is_link: bool,
     import: Import,
+    unknown: UnknownProperties,
 }
 
 fn properties<'a>(
@@ -101,7 +112,8 @@ fn generate_use(
         use turbine::{EntityType, EntityTypeRef, EntityTypeMut, EntityProperties, EntityLink, OptionalEntityLink};
         use turbine::{PropertyType as _, PropertyTypeRef as _, PropertyTypeMut as _};
         use turbine::{VersionedUrlRef, GenericEntityError};
-        use turbine::entity::Entity;
+        use turbine::entity::{Entity, EntityMetadata};
+        use turbine::EntityRecord;
         use turbine::{BaseUrl, url};
         use error_stack::{Result, Report, ResultExt as _};
         use hashbrown::HashMap;
@@ -113,25 +125,42 @@ fn generate_use(
 
 fn generate_properties_try_from_value(
     variant: Variant,
+    source: Source,
     properties: &BTreeMap<&BaseUrl, Property>,
+    unknown: UnknownProperties,
 ) -> TokenStream {
     shared::generate_properties_try_from_value(
         variant,
+        source,
         properties,
         &Ident::new("GenericEntityError", Span::call_site()),
         &quote!(Self),
+        unknown,
     )
 }
 
 fn generate_properties_is_valid_properties(
     variant: Variant,
     properties: &BTreeMap<&BaseUrl, Property>,
+    unknown: UnknownProperties,
 ) -> TokenStream {
     if variant != Variant::Owned {
         return quote!();
     }
 
-    if properties.is_empty() {
+    let check = (unknown == UnknownProperties::Reject).then(|| {
+        let keys = properties.keys().map(|base| base.as_str());
+
+        quote! {
+            const KEYS: &[&str] = &[#(#keys),*];
+
+            if !properties.keys().all(|key| KEYS.contains(&key.as_str())) {
+                return false;
+            }
+        }
+    });
+
+    if properties.is_empty() && check.is_none() {
         quote! {
             fn is_valid_value(_: &HashMap<String, serde_json::value::Value>) -> bool {
                 true
@@ -142,6 +171,8 @@ fn generate_properties_is_valid_properties(
 
         quote! {
             fn is_valid_value(properties: &HashMap<String, serde_json::value::Value>) -> bool {
+                #check
+
                 #body
             }
         }
@@ -151,8 +182,9 @@ fn generate_properties_is_valid_properties(
 fn generate_properties_convert(
     variant: Variant,
     properties: &BTreeMap<&BaseUrl, Property>,
+    extra: bool,
 ) -> TokenStream {
-    if properties.is_empty() {
+    if properties.is_empty() && !extra {
         match variant {
             Variant::Owned => {
                 // inner type is guaranteed to be `PhantomData`, so we can just create the type
@@ -177,22 +209,28 @@ fn generate_properties_convert(
             }
         }
     } else {
-        let name: Vec<_> = properties
+        let mut name: Vec<_> = properties
             .values()
-            .map(|Property { name, .. }| name)
+            .map(|Property { name, .. }| name.clone())
             .collect();
 
+        if extra {
+            name.push(Ident::new("extra", Span::call_site()));
+        }
+
         if variant == Variant::Owned {
             let as_mut = generate_property_object_conversion_body(
                 &quote!(PropertiesMut),
                 ConversionFunction::AsMut,
                 properties,
+                extra,
             );
 
             let as_ref = generate_property_object_conversion_body(
                 &quote!(PropertiesRef),
                 ConversionFunction::AsRef,
                 properties,
+                extra,
             );
 
             quote! {
@@ -213,6 +251,7 @@ fn generate_properties_convert(
                 &quote!(Properties),
                 ConversionFunction::IntoOwned { variant },
                 properties,
+                extra,
             );
 
             quote! {
@@ -250,7 +289,11 @@ fn generate_type(
         }
     }
 
-    if !properties.is_empty() {
+    // unknown properties are kept in `extra`, which is flattened into the properties
+    let extra = state.unknown == UnknownProperties::Preserve;
+    let has_fields = !properties.is_empty() || extra;
+
+    if has_fields {
         property_derives.push(format_ident!("Serialize"));
     }
 
@@ -287,51 +330,93 @@ fn generate_type(
         Variant::Ref | Variant::Mut => None,
     };
 
-    let mut fields = vec![quote!(pub properties: #properties_name #lifetime)];
+    // the `Mut` type holds the properties themselves, so that they can be added and removed, see
+    // `setter::generate`
+    let mut fields = vec![match variant {
+        Variant::Owned | Variant::Ref => quote!(pub properties: #properties_name #lifetime),
+        Variant::Mut => quote!(properties: turbine::entity::write::Backing<'a, Properties>),
+    }];
 
     if state.is_link {
         fields.push(quote!(pub link_data: #reference LinkData));
     }
 
-    let (body, try_from_value) = if properties.is_empty() {
-        if matches!(variant, Variant::Ref | Variant::Mut) {
-            state.import.phantom_data = true;
+    // metadata is never mutated through the typed entity
+    fields.push(match variant {
+        Variant::Owned => quote!(pub metadata: EntityMetadata),
+        Variant::Ref | Variant::Mut => quote!(pub metadata: &'a EntityMetadata),
+    });
 
-            (
-                quote!((pub PhantomData<&'a ()>);),
-                quote!(Ok(Self(PhantomData))),
-            )
-        } else {
-            (quote!(;), quote!(Ok(Self)))
+    let (body, try_from_value) = if has_fields {
+        let try_from_value =
+            generate_properties_try_from_value(variant, Source::Value, properties, state.unknown);
+
+        let mut fields: Vec<_> = properties
+            .iter()
+            .map(|(base, property)| {
+                generate_property(
+                    base,
+                    property,
+                    variant,
+                    Some(&Visibility::Public(Token![pub](Span::call_site()))),
+                    &mut state.import,
+                )
+            })
+            .collect();
+
+        if extra {
+            let extra = generate_extra_type(variant);
+
+            fields.push(quote! {
+                #[serde(flatten)]
+                pub extra: #extra
+            });
         }
-    } else {
-        let try_from_value = generate_properties_try_from_value(variant, properties);
-
-        let properties = properties.iter().map(|(base, property)| {
-            generate_property(
-                base,
-                property,
-                variant,
-                Some(&Visibility::Public(Token![pub](Span::call_site()))),
-                &mut state.import,
-            )
-        });
 
         (
             quote!({
-                #(#properties),*
+                #(#fields),*
             }),
             try_from_value,
         )
+    } else {
+        let check = generate_unknown_properties_check(state.unknown, Source::Value, properties);
+
+        if matches!(variant, Variant::Ref | Variant::Mut) {
+            state.import.phantom_data = true;
+
+            (
+                quote!((pub PhantomData<&'a ()>);),
+                quote!(#check Ok(Self(PhantomData))),
+            )
+        } else {
+            (quote!(;), quote!(#check Ok(Self)))
+        }
     };
 
-    let serialize_polyfill = properties
-        .is_empty()
-        .then(|| quote!(turbine::serialize_compat!(#properties_name #lifetime);));
+    let serialize_polyfill =
+        (!has_fields).then(|| quote!(turbine::serialize_compat!(#properties_name #lifetime);));
+
+    let try_from_raw = (variant == Variant::Ref).then(|| {
+        let body = if has_fields {
+            generate_properties_try_from_value(variant, Source::Raw, properties, state.unknown)
+        } else {
+            let check = generate_unknown_properties_check(state.unknown, Source::Raw, properties);
+
+            quote!(#check Ok(Self(PhantomData)))
+        };
+
+        quote! {
+            fn try_from_raw(properties: &turbine::raw::RawProperties<'a>) -> Result<Self, GenericEntityError> {
+                #body
+            }
+        }
+    });
 
-    let conversion = generate_properties_convert(variant, properties);
+    let conversion = generate_properties_convert(variant, properties, extra);
 
-    let is_valid_value = generate_properties_is_valid_properties(variant, properties);
+    let is_valid_value =
+        generate_properties_is_valid_properties(variant, properties, state.unknown);
 
     quote! {
         #property_derive
@@ -344,6 +429,8 @@ fn generate_type(
                 #try_from_value
             }
 
+            #try_from_raw
+
             #conversion
 
             #is_valid_value
@@ -374,8 +461,8 @@ fn generate_doc(entity: &EntityType) -> TokenStream {
     )
 }
 
-// we don't need pretty imports for these types, they are just tuples and are not meant to be
-// accessed directly
+// we don't need pretty imports for these types, they are only used in trait implementations and
+// are not meant to be accessed directly
 fn generate_absolute_import(location: &Location) -> TokenStream {
     let path = determine_import_path(location);
     let name = Ident::new(&location.name.value, Span::call_site());
@@ -383,23 +470,50 @@ fn generate_absolute_import(location: &Location) -> TokenStream {
     quote!(crate #(:: #path)* :: #name)
 }
 
-fn generate_type_url_inherits_from(entity: &EntityType, resolver: &NameResolver) -> TokenStream {
-    let all_of = entity
-        .inherits_from()
-        .all_of()
-        .iter()
-        .filter_map(|reference| {
-            let url = reference.url();
+fn generate_type_url_ancestors(entity: &EntityType, resolver: &NameResolver) -> TokenStream {
+    // an ancestor reachable through multiple parents, e.g. in a diamond, is only listed once
+    let ancestors: BTreeSet<_> = resolver.facts().ancestors(entity.id()).collect();
 
-            if resolver.facts().should_skip(url) {
-                return None;
-            }
+    // root types use the default of `TypeUrl`, which has no ancestors
+    if ancestors.is_empty() {
+        return quote!();
+    }
+
+    let ancestors = ancestors.into_iter().map(|url| {
+        let base_url = url.base_url.as_str();
+        let version = url.version;
+
+        quote!(url!(#base_url / v / #version))
+    });
+
+    quote!(const ANCESTORS: &'static [VersionedUrlRef<'static>] = &[#(#ancestors),*];)
+}
+
+/// Implement `InheritsFrom` for every ancestor, which is generated, for all variants of the type.
+fn generate_inherits_from(
+    entity: &EntityType,
+    location: &Location,
+    resolver: &NameResolver,
+) -> TokenStream {
+    let name = Ident::new(&location.name.value, Span::call_site());
+    let name_ref = Ident::new(&location.name_ref.value, Span::call_site());
+    let name_mut = Ident::new(&location.name_mut.value, Span::call_site());
+
+    let impls = resolver
+        .facts()
+        .ancestors(entity.id())
+        .filter(|url| !resolver.facts().should_skip(url))
+        .map(|url| {
+            let parent = generate_absolute_import(&resolver.location(url));
 
-            let location = resolver.location(url);
-            Some(generate_absolute_import(&location))
+            quote! {
+                impl turbine::InheritsFrom<#parent> for #name {}
+                impl turbine::InheritsFrom<#parent> for #name_ref<'_> {}
+                impl turbine::InheritsFrom<#parent> for #name_mut<'_> {}
+            }
         });
 
-    quote!(type InheritsFrom = (#(#all_of, <#all_of as TypeUrl>::InheritsFrom,)*);)
+    quote!(#(#impls)*)
 }
 
 fn generate_owned(
@@ -443,13 +557,14 @@ fn generate_owned(
         }
     };
 
-    let inherits_from = generate_type_url_inherits_from(entity, resolver);
+    let ancestors = generate_type_url_ancestors(entity, resolver);
+    let keys = properties.keys().map(|base| base.as_str());
 
     quote! {
         #def
 
         impl TypeUrl for #name {
-            #inherits_from
+            #ancestors
 
             const ID: VersionedUrlRef<'static>  = url!(#base_url / v / #version);
         }
@@ -460,15 +575,17 @@ fn generate_owned(
 
             fn as_mut(&mut self) -> Self::Mut<'_> {
                 #name_mut {
-                    properties: self.properties.as_mut(),
-                    #(#link_data: &mut self.link_data)*
+                    properties: turbine::entity::write::Backing::Owned(&mut self.properties),
+                    #(#link_data: &mut self.link_data,)*
+                    metadata: &self.metadata,
                 }
             }
 
             fn as_ref(&self) -> Self::Ref<'_> {
                 #name_ref {
                     properties: self.properties.as_ref(),
-                    #(#link_data: &self.link_data)*
+                    #(#link_data: &self.link_data,)*
+                    metadata: &self.metadata,
                 }
             }
         }
@@ -477,7 +594,7 @@ fn generate_owned(
             type Error = GenericEntityError;
 
             fn try_from_entity(value: Entity) -> Option<Result<Self, Self::Error>> {
-                if Self::ID != value.metadata.entity_type_id {
+                if !value.metadata.entity_type_ids.is_instance_of(Self::ID, crate::find_inherits_from) {
                     return None;
                 }
 
@@ -493,6 +610,7 @@ fn generate_owned(
                             Self {
                                 properties,
                                 #(#link_data,)*
+                                metadata: value.metadata,
                             }
                         )
                     )
@@ -500,7 +618,7 @@ fn generate_owned(
             }
 
             fn is_valid_entity(value: &Entity) -> bool {
-                Self::ID == value.metadata.entity_type_id &&
+                value.metadata.entity_type_ids.is_instance_of(Self::ID, crate::find_inherits_from) &&
                     #(value.#link_data.is_some() &&)*
                     Properties::is_valid_value(&value.properties.0)
             }
@@ -509,11 +627,19 @@ fn generate_owned(
         impl EntityProperties for #name {
             type Properties = Properties;
 
+            const KEYS: &'static [&'static str] = &[#(#keys),*];
+
             fn properties(&self) -> &Self::Properties {
                 &self.properties
             }
         }
 
+        impl EntityRecord for #name {
+            fn metadata(&self) -> &EntityMetadata {
+                &self.metadata
+            }
+        }
+
         #entity_link
     }
 }
@@ -558,13 +684,14 @@ fn generate_ref(
         }
     };
 
-    let inherits_from = generate_type_url_inherits_from(entity, resolver);
+    let ancestors = generate_type_url_ancestors(entity, resolver);
+    let keys = properties.keys().map(|base| base.as_str());
 
     quote! {
         #def
 
         impl TypeUrl for #name_ref<'_> {
-            #inherits_from
+            #ancestors
 
             const ID: VersionedUrlRef<'static>  = url!(#base_url / v / #version);
         }
@@ -575,7 +702,8 @@ fn generate_ref(
             fn into_owned(self) -> Self::Owned {
                 #name {
                     properties: self.properties.into_owned(),
-                    #(#link_data: self.link_data.clone())*
+                    #(#link_data: self.link_data.clone(),)*
+                    metadata: self.metadata.clone(),
                 }
             }
         }
@@ -584,7 +712,7 @@ fn generate_ref(
             type Error = GenericEntityError;
 
             fn try_from_entity(value: &'a Entity) -> Option<Result<Self, Self::Error>> {
-                if Self::Owned::ID != value.metadata.entity_type_id {
+                if !value.metadata.entity_type_ids.is_instance_of(Self::Owned::ID, crate::find_inherits_from) {
                     return None;
                 }
 
@@ -601,6 +729,32 @@ fn generate_ref(
                             Self {
                                 properties,
                                 #(#link_data,)*
+                                metadata: &value.metadata,
+                            }
+                        )
+                    )
+                }
+            }
+
+            fn try_from_raw_entity(value: &'a turbine::raw::RawEntity<'a>) -> Option<Result<Self, Self::Error>> {
+                if !value.metadata.entity_type_ids.is_instance_of(Self::Owned::ID, crate::find_inherits_from) {
+                    return None;
+                }
+
+                let properties = PropertiesRef::try_from_raw(&value.properties);
+                #(let #link_data = value.link_data
+                    .as_ref()
+                    .ok_or_else(|| Report::new(GenericEntityError::ExpectedLinkData));
+                )*
+
+                match turbine::fold_tuple_reports((properties, #(#link_data)*)) {
+                    Err(error) => Some(Err(error)),
+                    Ok((properties, #(#link_data,)*)) => Some(
+                        Ok(
+                            Self {
+                                properties,
+                                #(#link_data,)*
+                                metadata: &value.metadata,
                             }
                         )
                     )
@@ -611,11 +765,19 @@ fn generate_ref(
         impl<'a> EntityProperties for #name_ref<'a> {
             type Properties = PropertiesRef<'a>;
 
+            const KEYS: &'static [&'static str] = &[#(#keys),*];
+
             fn properties(&self) -> &Self::Properties {
                 &self.properties
             }
         }
 
+        impl EntityRecord for #name_ref<'_> {
+            fn metadata(&self) -> &EntityMetadata {
+                self.metadata
+            }
+        }
+
         #entity_link
     }
 }
@@ -631,6 +793,7 @@ fn generate_mut(
     let name_mut = Ident::new(&location.name_mut.value, Span::call_site());
 
     let def = generate_type(entity, Variant::Mut, location, properties, state);
+    let setters = setter::generate(location, properties);
 
     let base_url = entity.id().base_url.as_str();
     let version = entity.id().version;
@@ -660,13 +823,16 @@ fn generate_mut(
         }
     };
 
-    let inherits_from = generate_type_url_inherits_from(entity, resolver);
+    let ancestors = generate_type_url_ancestors(entity, resolver);
+    let keys = properties.keys().map(|base| base.as_str());
 
     quote! {
         #def
 
+        #setters
+
         impl TypeUrl for #name_mut<'_> {
-            #inherits_from
+            #ancestors
 
             const ID: VersionedUrlRef<'static>  = url!(#base_url / v / #version);
         }
@@ -675,9 +841,19 @@ fn generate_mut(
             type Owned = #name;
 
             fn into_owned(self) -> Self::Owned {
+                let properties = match self.properties {
+                    turbine::entity::write::Backing::Entity(properties) => {
+                        PropertiesMut::try_from_value(properties)
+                            .expect("properties should be validated on creation and on every write")
+                            .into_owned()
+                    }
+                    turbine::entity::write::Backing::Owned(properties) => properties.clone(),
+                };
+
                 #name {
-                    properties: self.properties.into_owned(),
-                    #(#link_data: self.link_data.clone())*
+                    properties,
+                    #(#link_data: self.link_data.clone(),)*
+                    metadata: self.metadata.clone(),
                 }
             }
         }
@@ -686,11 +862,12 @@ fn generate_mut(
             type Error = GenericEntityError;
 
             fn try_from_entity(value: &'a mut Entity) -> Option<Result<Self, Self::Error>> {
-                if Self::Owned::ID != value.metadata.entity_type_id {
+                if !value.metadata.entity_type_ids.is_instance_of(Self::Owned::ID, crate::find_inherits_from) {
                     return None;
                 }
 
-                let properties = PropertiesMut::try_from_value(&mut value.properties.0);
+                // only validated, the properties are borrowed on demand, see `properties_mut`
+                let properties = PropertiesMut::try_from_value(&mut value.properties.0).map(drop);
                 #(let #link_data = value.link_data
                     .as_mut()
                     .ok_or_else(|| Report::new(GenericEntityError::ExpectedLinkData));
@@ -698,11 +875,12 @@ fn generate_mut(
 
                 match turbine::fold_tuple_reports((properties, #(#link_data)*)) {
                     Err(error) => Some(Err(error)),
-                    Ok((properties, #(#link_data,)*)) => Some(
+                    Ok(((), #(#link_data,)*)) => Some(
                         Ok(
                             Self {
-                                properties,
+                                properties: turbine::entity::write::Backing::Entity(&mut value.properties.0),
                                 #(#link_data,)*
+                                metadata: &value.metadata,
                             }
                         )
                     )
@@ -711,13 +889,21 @@ fn generate_mut(
         }
 
         impl<'a> EntityProperties for #name_mut<'a> {
-            type Properties = PropertiesMut<'a>;
+            type Properties = turbine::entity::write::Backing<'a, Properties>;
+
+            const KEYS: &'static [&'static str] = &[#(#keys),*];
 
             fn properties(&self) -> &Self::Properties {
                 &self.properties
             }
         }
 
+        impl EntityRecord for #name_mut<'_> {
+            fn metadata(&self) -> &EntityMetadata {
+                self.metadata
+            }
+        }
+
         #entity_link
     }
 }
@@ -736,7 +922,19 @@ pub(crate) fn generate(entity: &EntityType, resolver: &NameResolver) -> TokenStr
     // need to sort, as otherwise results might vary between invocations
     references.sort();
 
+    let links = link::links(entity, resolver);
+    let links_reserved = link::reserved(&links);
+    let builder_reserved = builder::name(&location);
+    let path_reserved = path::name(&location);
+    let version_reserved = version::name(&location);
+    let like_reserved = like::name(&location);
+
     let mut reserved = RESERVED.to_vec();
+    reserved.extend(links_reserved.iter().map(String::as_str));
+    reserved.push(&builder_reserved);
+    reserved.push(&path_reserved);
+    reserved.extend(version_reserved.as_deref());
+    reserved.push(&like_reserved);
     reserved.push(&location.name.value);
     reserved.push(&location.name_ref.value);
     reserved.push(&location.name_mut.value);
@@ -765,11 +963,29 @@ pub(crate) fn generate(entity: &EntityType, resolver: &NameResolver) -> TokenStr
             box_: false,
             phantom_data: false,
         },
+        unknown: resolver.unknown_properties(),
     };
 
+    if !links.is_empty() {
+        state.import.vec = true;
+    }
+
     let owned = generate_owned(entity, &location, &properties, &mut state, resolver);
     let ref_ = generate_ref(entity, &location, &properties, &mut state, resolver);
     let mut_ = generate_mut(entity, &location, &properties, &mut state, resolver);
+    let link_accessors = link::generate_accessors(&location, &links);
+    let links = link::generate(&location, &links);
+    let builder = builder::generate(
+        &location,
+        &properties,
+        state.is_link,
+        state.unknown == UnknownProperties::Preserve,
+    );
+    let path = path::generate(&location, &properties);
+    let inherits_from = generate_inherits_from(entity, &location, resolver);
+    let like = like::generate(entity, &location, &properties, resolver);
+    let conversion = version::generate_conversion(entity, &location, resolver, state.is_link);
+    let any_version = version::generate_any_version(entity, &location, resolver);
 
     let mod_ = generate_mod(&location.kind, resolver);
     let use_ = generate_use(&references, &locations, &state);
@@ -781,6 +997,21 @@ pub(crate) fn generate(entity: &EntityType, resolver: &NameResolver) -> TokenStr
         #ref_
         #mut_
 
+        #inherits_from
+
+        #like
+
+        #conversion
+
+        #any_version
+
+        #links
+        #link_accessors
+
+        #builder
+
+        #path
+
         #mod_
     }
 }
@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use type_system::url::BaseUrl;
+
+use crate::{
+    name::Location,
+    shared::{Property, PropertyKind},
+};
+
+pub(super) fn name(location: &Location) -> String {
+    format!("{}Builder", location.name.value)
+}
+
+/// A single field of the builder, which is either a property or the link data.
+struct Field {
+    name: Ident,
+    /// Type accepted by the setter, boxed properties are boxed once the builder is finished.
+    type_: TokenStream,
+    /// Type parameter used to track if the field has been set, `None` for optional fields.
+    parameter: Option<Ident>,
+    doc: Option<String>,
+}
+
+fn fields(properties: &BTreeMap<&BaseUrl, Property>, is_link: bool) -> Vec<Field> {
+    let mut index = 0;
+    let mut parameter = |required: bool| {
+        required.then(|| {
+            let ident = format_ident!("T{index}");
+            index += 1;
+            ident
+        })
+    };
+
+    let mut fields: Vec<_> = properties
+        .iter()
+        .map(|(base, property)| {
+            let type_ = &property.type_;
+
+            let type_ = match property.kind {
+                PropertyKind::Array => quote!(Vec<#type_>),
+                PropertyKind::Plain | PropertyKind::Boxed => quote!(#type_),
+            };
+
+            Field {
+                name: property.name.clone(),
+                type_,
+                parameter: parameter(property.required),
+                doc: Some(format!("Set the value of `{}`", base.as_str())),
+            }
+        })
+        .collect();
+
+    if is_link {
+        fields.push(Field {
+            name: Ident::new("link_data", Span::call_site()),
+            type_: quote!(LinkData),
+            parameter: parameter(true),
+            doc: None,
+        });
+    }
+
+    fields
+}
+
+fn generate_setter(builder: &Ident, fields: &[Field], field: &Field) -> TokenStream {
+    let parameters: Vec<_> = fields
+        .iter()
+        .filter_map(|field| field.parameter.as_ref())
+        .collect();
+
+    let Field {
+        name,
+        type_,
+        parameter,
+        doc,
+    } = field;
+
+    let doc = doc.as_ref().map(|doc| quote!(#[doc = #doc]));
+
+    let Some(parameter) = parameter else {
+        return quote! {
+            impl<#(#parameters),*> #builder<#(#parameters),*> {
+                #doc
+                #[must_use]
+                pub fn #name(mut self, value: #type_) -> Self {
+                    self.#name = Some(value);
+                    self
+                }
+            }
+        };
+    };
+
+    let output = fields.iter().filter_map(|other| {
+        let other_parameter = other.parameter.as_ref()?;
+
+        if other_parameter == parameter {
+            Some(quote!(turbine::builder::Set<#type_>))
+        } else {
+            Some(quote!(#other_parameter))
+        }
+    });
+
+    let assign = fields.iter().map(|other| {
+        let other_name = &other.name;
+
+        if other_name == name {
+            quote!(#name: turbine::builder::Set(value))
+        } else {
+            quote!(#other_name: self.#other_name)
+        }
+    });
+
+    quote! {
+        impl<#(#parameters),*> #builder<#(#parameters),*> {
+            #doc
+            #[must_use]
+            pub fn #name(self, value: #type_) -> #builder<#(#output),*> {
+                #builder {
+                    #(#assign),*
+                }
+            }
+        }
+    }
+}
+
+pub(super) fn generate(
+    location: &Location,
+    properties: &BTreeMap<&BaseUrl, Property>,
+    is_link: bool,
+    extra: bool,
+) -> TokenStream {
+    let name = Ident::new(&location.name.value, Span::call_site());
+    let builder = Ident::new(&self::name(location), Span::call_site());
+
+    let fields = fields(properties, is_link);
+
+    let definition = fields.iter().map(
+        |Field {
+             name,
+             type_,
+             parameter,
+             ..
+         }| {
+            parameter.as_ref().map_or_else(
+                || quote!(#name: Option<#type_>),
+                |parameter| quote!(#name: #parameter),
+            )
+        },
+    );
+
+    let defaults = fields.iter().filter_map(|Field { parameter, .. }| {
+        parameter
+            .as_ref()
+            .map(|parameter| quote!(#parameter = turbine::builder::Missing))
+    });
+
+    let initial = fields.iter().map(
+        |Field {
+             name, parameter, ..
+         }| {
+            if parameter.is_some() {
+                quote!(#name: turbine::builder::Missing)
+            } else {
+                quote!(#name: None)
+            }
+        },
+    );
+
+    let setters = fields
+        .iter()
+        .map(|field| generate_setter(&builder, &fields, field));
+
+    let complete = fields.iter().filter_map(
+        |Field {
+             type_, parameter, ..
+         }| {
+            parameter
+                .as_ref()
+                .map(|_| quote!(turbine::builder::Set<#type_>))
+        },
+    );
+
+    let property_names: Vec<_> = properties.values().map(|property| &property.name).collect();
+    let property_values = properties.values().map(|property| {
+        let name = &property.name;
+
+        let value = match (&property.kind, property.required) {
+            (PropertyKind::Boxed, true) => quote!(Box::new(self.#name.0)),
+            (PropertyKind::Boxed, false) => quote!(self.#name.map(Box::new)),
+            (PropertyKind::Array | PropertyKind::Plain, true) => quote!(self.#name.0),
+            (PropertyKind::Array | PropertyKind::Plain, false) => quote!(self.#name),
+        };
+
+        if property.is_nullable() {
+            // values which have not been set are absent, instead of `null`
+            quote!(turbine::Nullable::from(#value))
+        } else {
+            value
+        }
+    });
+
+    let extra = extra.then(|| quote!(extra: turbine::entity::ExtraProperties::default(),));
+
+    let properties = if property_names.is_empty() && extra.is_none() {
+        quote!(Properties)
+    } else {
+        quote!(Properties { #(#property_names: #property_values,)* #extra })
+    };
+
+    let link_data = if is_link {
+        quote!(Some(self.link_data.0))
+    } else {
+        quote!(None)
+    };
+
+    let doc = format!(
+        "Builder for [`{name}`], [`{builder}::build`] is only available once every required \
+         property has been set."
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone)]
+        #[must_use]
+        pub struct #builder<#(#defaults),*> {
+            #(#definition),*
+        }
+
+        #(#setters)*
+
+        impl #builder<#(#complete),*> {
+            /// Create the payload to create a new entity of this type.
+            ///
+            /// # Errors
+            ///
+            /// if the properties cannot be serialized into their wire representation
+            pub fn build(self) -> Result<turbine::entity::CreateEntity, turbine::WireError> {
+                let properties = #properties;
+
+                Ok(turbine::entity::CreateEntity {
+                    properties: turbine::entity::EntityProperties::from_typed(&properties)?,
+                    entity_type_id: <#name as TypeUrl>::ID.into_owned(),
+                    link_data: #link_data,
+                })
+            }
+        }
+
+        impl #name {
+            /// Create a builder for a new entity of this type.
+            pub fn builder() -> #builder {
+                #builder {
+                    #(#initial),*
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use type_system::{url::BaseUrl, EntityType, ValueOrArray};
+
+use crate::{
+    name::{Location, NameResolver},
+    shared::{determine_import_path, Property, PropertyKind},
+    AnyType,
+};
+
+/// Name of the trait with accessors for the properties of the entity type, which is implemented
+/// by the entity type and every entity type inheriting from it.
+pub(super) fn name(location: &Location) -> String {
+    format!("{}Like", location.name.value)
+}
+
+fn return_type(kind: &PropertyKind, type_: &Ident, required: bool, nullable: bool) -> TokenStream {
+    let type_ = match kind {
+        PropertyKind::Array => quote!([#type_]),
+        PropertyKind::Plain | PropertyKind::Boxed => quote!(#type_),
+    };
+
+    if required {
+        quote!(&#type_)
+    } else if nullable {
+        quote!(turbine::Nullable<&#type_>)
+    } else {
+        quote!(Option<&#type_>)
+    }
+}
+
+fn access(
+    Property {
+        name,
+        kind,
+        required,
+        ..
+    }: &Property,
+) -> TokenStream {
+    let field = quote!(self.properties.#name);
+
+    match (required, kind) {
+        (true, PropertyKind::Plain) => quote!(&#field),
+        (true, PropertyKind::Boxed) => quote!(&*#field),
+        (true, PropertyKind::Array) => quote!(#field.as_slice()),
+        (false, PropertyKind::Plain) => quote!(#field.as_ref()),
+        (false, PropertyKind::Boxed | PropertyKind::Array) => quote!(#field.as_deref()),
+    }
+}
+
+/// Implement the trait of `parent` for `entity`, the accessors are named after the properties of
+/// `parent`, but read the fields of `entity`.
+///
+/// Returns the reason if a property of `parent` cannot be read from `entity`, e.g. because it is
+/// optional on `entity`, but required on `parent`.
+fn generate_impl(
+    entity: &EntityType,
+    target: &Ident,
+    properties: &BTreeMap<&BaseUrl, Property>,
+    parent: &EntityType,
+    trait_: &TokenStream,
+    resolver: &NameResolver,
+) -> Result<TokenStream, String> {
+    // same order as in `entity::generate`, otherwise clashing names might be suffixed differently
+    let property_type_references = parent.property_type_references();
+    let mut references: Vec<_> = property_type_references
+        .iter()
+        .map(|reference| reference.url())
+        .collect();
+    references.sort();
+
+    let names = resolver.property_names(references.iter().copied());
+
+    let mut parent_properties: Vec<_> = parent.properties().iter().collect();
+    parent_properties.sort_by_key(|(base, _)| *base);
+
+    let accessors = parent_properties
+        .into_iter()
+        .map(|(base, value)| {
+            let url = match value {
+                ValueOrArray::Value(value) => value.url(),
+                ValueOrArray::Array(value) => value.items().url(),
+            };
+            let name = Ident::new(&names[url].0, Span::call_site());
+
+            if entity.properties().get(base) != Some(value) {
+                return Err(format!("`{name}` is of a different type"));
+            }
+
+            let property = &properties[base];
+            let required = parent.required().contains(base);
+            if required && !property.required {
+                return Err(format!(
+                    "`{name}` is optional, but required by the ancestor"
+                ));
+            }
+
+            let return_type =
+                return_type(&property.kind, &property.type_, required, property.nullable);
+            let mut access = access(property);
+            if !required && property.required {
+                access = if property.nullable {
+                    quote!(turbine::Nullable::Value(#access))
+                } else {
+                    quote!(Some(#access))
+                };
+            }
+
+            Ok(quote! {
+                fn #name(&self) -> #return_type {
+                    #access
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(quote! {
+        impl #trait_ for #target {
+            #(#accessors)*
+        }
+    })
+}
+
+/// Generate the trait of the entity type, its implementation for the entity type itself, and the
+/// implementations of the traits of every generated ancestor.
+pub(super) fn generate(
+    entity: &EntityType,
+    location: &Location,
+    properties: &BTreeMap<&BaseUrl, Property>,
+    resolver: &NameResolver,
+) -> TokenStream {
+    let name = Ident::new(&location.name.value, Span::call_site());
+    let trait_ = format_ident!("{}", self::name(location));
+
+    let doc = format!(
+        "Properties of [`{name}`], implemented by [`{name}`] and every entity type inheriting \
+         from it."
+    );
+    let doc_owned = "Only implemented by the owned entity types, the accessors return references \
+                     to owned values, which the `Ref` and `Mut` variants do not hold.";
+
+    let accessors = properties.values().map(
+        |Property {
+             name,
+             type_,
+             kind,
+             required,
+             nullable,
+             ..
+         }| {
+            let return_type = return_type(kind, type_, *required, *nullable);
+
+            quote!(fn #name(&self) -> #return_type;)
+        },
+    );
+
+    let own = generate_impl(
+        entity,
+        &name,
+        properties,
+        entity,
+        &quote!(#trait_),
+        resolver,
+    )
+    .ok();
+
+    // ancestors, whose trait cannot be implemented, are listed in the documentation instead
+    let mut missing = vec![];
+
+    let ancestors: Vec<_> = resolver
+        .facts()
+        .ancestors(entity.id())
+        .filter(|url| !resolver.facts().should_skip(url))
+        .filter_map(|url| match resolver.get(url) {
+            Some(AnyType::Entity(parent)) => Some(parent),
+            _ => None,
+        })
+        .filter_map(|parent| {
+            let location = resolver.location(parent.id());
+            let path = determine_import_path(&location);
+            let trait_ = format_ident!("{}", self::name(&location));
+
+            generate_impl(
+                entity,
+                &name,
+                properties,
+                parent,
+                &quote!(crate #(:: #path)* :: #trait_),
+                resolver,
+            )
+            .map_err(|reason| {
+                let path = path.iter().map(ToString::to_string).join("::");
+
+                missing.push(format!("- [`{trait_}`](crate::{path}::{trait_}): {reason}"));
+            })
+            .ok()
+        })
+        .collect();
+
+    let doc_missing = (!missing.is_empty()).then(|| {
+        let doc = format!(
+            "[`{name}`] does not implement the traits of the following ancestors, as one of their \
+             properties cannot be read from [`{name}`]:"
+        );
+
+        quote! {
+            #[doc = ""]
+            #[doc = #doc]
+            #[doc = ""]
+            #(#[doc = #missing])*
+        }
+    });
+
+    quote! {
+        #[doc = #doc]
+        #[doc = ""]
+        #[doc = #doc_owned]
+        #doc_missing
+        pub trait #trait_ {
+            #(#accessors)*
+        }
+
+        #own
+
+        #(#ancestors)*
+    }
+}
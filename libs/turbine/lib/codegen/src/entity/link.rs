@@ -0,0 +1,230 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use type_system::{url::VersionedUrl, EntityType, EntityTypeReference};
+
+use crate::{
+    entity::generate_absolute_import,
+    name::{Location, NameResolver},
+};
+
+/// A single link constraint of an entity type, which can be represented in the generated code.
+pub(super) struct Link<'a> {
+    url: &'a VersionedUrl,
+    location: Location<'a>,
+    destinations: Option<Vec<Location<'a>>>,
+    ordered: bool,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+}
+
+impl Link<'_> {
+    fn destination_name(&self) -> Ident {
+        format_ident!("{}Destination", self.location.name.value)
+    }
+}
+
+/// Collect all link constraints, sorted by the URL of the link type, so that the output is stable.
+///
+/// Constraints to link types that are not part of the generated crate are skipped, as we are
+/// unable to name them. If one of the destinations is not part of the generated crate, the
+/// destination is treated as unconstrained.
+pub(super) fn links<'a>(entity: &'a EntityType, resolver: &'a NameResolver) -> Vec<Link<'a>> {
+    let mut links: Vec<_> = entity
+        .links()
+        .iter()
+        .filter(|(url, _)| !resolver.facts().should_skip(url) && resolver.contains(url))
+        .map(|(url, constraint)| {
+            let array = constraint.array();
+
+            let destinations = array.items().as_ref().and_then(|one_of| {
+                let mut urls: Vec<_> = one_of
+                    .one_of()
+                    .iter()
+                    .map(EntityTypeReference::url)
+                    .collect();
+                urls.sort();
+                urls.dedup();
+
+                let unconstrained = urls.is_empty()
+                    || urls
+                        .iter()
+                        .any(|url| resolver.facts().should_skip(url) || !resolver.contains(url));
+
+                (!unconstrained)
+                    .then(|| urls.into_iter().map(|url| resolver.location(url)).collect())
+            });
+
+            Link {
+                url,
+                location: resolver.location(url),
+                destinations,
+                ordered: constraint.ordered(),
+                min_items: array.min_items(),
+                max_items: array.max_items(),
+            }
+        })
+        .collect();
+
+    links.sort_by_key(|link| link.url);
+
+    links
+}
+
+/// Names of all types that will be created by [`generate`], these need to be reserved, so that
+/// imports do not collide with them.
+pub(super) fn reserved(links: &[Link]) -> Vec<String> {
+    links
+        .iter()
+        .filter(|link| matches!(&link.destinations, Some(destinations) if destinations.len() > 1))
+        .map(|link| link.destination_name().to_string())
+        .collect()
+}
+
+fn generate_destination(link: &Link) -> (TokenStream, TokenStream) {
+    let Some(destinations) = &link.destinations else {
+        return (quote!(&'a Entity), quote!());
+    };
+
+    if let [destination] = destinations.as_slice() {
+        let destination = generate_absolute_import(destination);
+
+        return (quote!(<#destination as Type>::Ref<'a>), quote!());
+    }
+
+    let name = link.destination_name();
+    let doc = format!(
+        "Entity types allowed as the destination of [`{}`]",
+        link.location.name.value
+    );
+
+    let (variants, conversions): (Vec<_>, Vec<_>) = destinations
+        .iter()
+        .enumerate()
+        .map(|(index, location)| {
+            let variant = format_ident!("Variant{index}");
+            let type_ = generate_absolute_import(location);
+            let type_ = quote!(<#type_ as Type>::Ref<'a>);
+
+            (quote!(#variant(#type_)), quote! {
+                if let Some(value) = <#type_ as EntityTypeRef<'a>>::try_from_entity(entity) {
+                    return Some(value.map(Self::#variant));
+                }
+            })
+        })
+        .unzip();
+
+    let def = quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum #name<'a> {
+            #(#variants),*
+        }
+
+        impl<'a> turbine::link::LinkDestination<'a> for #name<'a> {
+            fn try_from_entity(entity: &'a Entity) -> Option<Result<Self, GenericEntityError>> {
+                #(#conversions)*
+
+                None
+            }
+        }
+    };
+
+    (quote!(#name<'a>), def)
+}
+
+pub(super) fn generate(location: &Location, links: &[Link]) -> TokenStream {
+    let name = Ident::new(&location.name.value, Span::call_site());
+
+    let impls = links.iter().map(|link| {
+        let link_type = generate_absolute_import(&link.location);
+        let (destination, def) = generate_destination(link);
+
+        let ordered = link.ordered;
+        let min_items = link
+            .min_items
+            .map_or_else(|| quote!(None), |min| quote!(Some(#min)));
+        let max_items = link
+            .max_items
+            .map_or_else(|| quote!(None), |max| quote!(Some(#max)));
+
+        quote! {
+            #def
+
+            impl turbine::link::OutgoingLink<#link_type> for #name {
+                type Destination<'a> = #destination;
+
+                const ORDERED: bool = #ordered;
+
+                const MIN_ITEMS: Option<usize> = #min_items;
+                const MAX_ITEMS: Option<usize> = #max_items;
+            }
+        }
+    });
+
+    quote!(#(#impls)*)
+}
+
+/// Generate the `links` method on every variant, which resolves the links of the entity through
+/// its own id, as recorded in the metadata.
+pub(super) fn generate_accessors(location: &Location, links: &[Link]) -> TokenStream {
+    if links.is_empty() {
+        return quote!();
+    }
+
+    let name = Ident::new(&location.name.value, Span::call_site());
+    let doc =
+        format!("Resolve all outgoing links of type `L` of this entity, see [`{name}::links`].");
+
+    // the `Ref` and `Mut` variant only borrow the metadata, links are resolved the same way
+    let borrowed = [&location.name_ref, &location.name_mut].map(|variant| {
+        let variant = Ident::new(&variant.value, Span::call_site());
+
+        quote! {
+            impl #variant<'_> {
+                #[doc = #doc]
+                ///
+                /// # Errors
+                ///
+                /// if a link or destination is malformed, or the cardinality constraints are violated
+                pub fn links<'a, L>(
+                    &self,
+                    source: &'a (impl turbine::link::LinkSource + ?Sized)
+                ) -> Result<turbine::link::Links<'a, #name, L>, turbine::link::LinkError>
+                where
+                    #name: turbine::link::OutgoingLink<L>,
+                    L: Type + 'a,
+                    L::Ref<'a>: EntityTypeRef<'a>,
+                {
+                    turbine::link::links::<#name, L, _>(EntityRecord::entity_id(self), source)
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #name {
+            /// Resolve all outgoing links of type `L` of this entity, e.g.
+            /// `person.links::<FriendOf>(&index)`.
+            ///
+            /// The link entities, as well as the entities they point to, are validated against the
+            /// link constraints of this entity type.
+            ///
+            /// # Errors
+            ///
+            /// if a link or destination is malformed, or the cardinality constraints are violated
+            pub fn links<'a, L>(
+                &self,
+                source: &'a (impl turbine::link::LinkSource + ?Sized)
+            ) -> Result<turbine::link::Links<'a, Self, L>, turbine::link::LinkError>
+            where
+                Self: turbine::link::OutgoingLink<L>,
+                L: Type + 'a,
+                L::Ref<'a>: EntityTypeRef<'a>,
+            {
+                turbine::link::links::<Self, L, _>(EntityRecord::entity_id(self), source)
+            }
+        }
+
+        #(#borrowed)*
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use type_system::url::BaseUrl;
+
+use crate::{
+    name::Location,
+    shared::{Property, PropertyKind},
+};
+
+/// `push_x` and `remove_x` of an array property.
+fn generate_array(
+    base: &BaseUrl,
+    Property {
+        name,
+        type_,
+        required,
+        items,
+        ..
+    }: &Property,
+) -> TokenStream {
+    let index = base.as_str();
+
+    let push = format_ident!("push_{name}");
+    let push_doc =
+        format!("Append a value to `{name}`, the value is validated before it is written.");
+
+    let remove = format_ident!("remove_{name}");
+    let remove_doc = format!("Remove the value at `index` from `{name}`.");
+
+    // arrays are never nullable, an optional array is an `Option<Vec<T>>`
+    let (values, values_mut, values_or_insert) = if *required {
+        (
+            quote!(Some(&properties.#name)),
+            quote!(Some(&mut properties.#name)),
+            quote!(properties.#name),
+        )
+    } else {
+        (
+            quote!(properties.#name.as_ref()),
+            quote!(properties.#name.as_mut()),
+            quote!(properties.#name.get_or_insert_with(Vec::new)),
+        )
+    };
+
+    let len = quote! {
+        match &self.properties {
+            turbine::entity::write::Backing::Entity(properties) => {
+                turbine::entity::write::len(properties, #index)
+            }
+            turbine::entity::write::Backing::Owned(properties) => #values.map_or(0, Vec::len),
+        }
+    };
+
+    let push_count = items.generate_ensure(&quote!(len + 1)).map(|ensure| {
+        quote! {
+            let len = #len;
+            #ensure
+                .change_context(GenericEntityError::Property)
+                .attach_printable(#index)?;
+        }
+    });
+
+    let remove_count = items.generate_ensure(&quote!(len - 1)).map(|ensure| {
+        quote! {
+            let len = #len;
+            if index < len {
+                #ensure
+                    .change_context(GenericEntityError::Property)
+                    .attach_printable(#index)?;
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #push_doc]
+        ///
+        /// # Errors
+        ///
+        /// - if the value is not valid for the property type
+        /// - if the number of items would violate `minItems` or `maxItems`
+        pub fn #push(&mut self, value: #type_) -> Result<(), GenericEntityError> {
+            #push_count
+
+            let encoded = turbine::entity::write::encode(#index, &value, <#type_>::is_valid_value)?;
+            match &mut self.properties {
+                turbine::entity::write::Backing::Entity(properties) => {
+                    turbine::entity::write::push(properties, #index, encoded);
+                }
+                turbine::entity::write::Backing::Owned(properties) => {
+                    #values_or_insert.push(value);
+                }
+            }
+
+            Ok(())
+        }
+
+        #[doc = #remove_doc]
+        ///
+        /// Returns `false` if there is no value at `index`.
+        ///
+        /// # Errors
+        ///
+        /// if the number of items would violate `minItems` or `maxItems`
+        pub fn #remove(&mut self, index: usize) -> Result<bool, GenericEntityError> {
+            #remove_count
+
+            let removed = match &mut self.properties {
+                turbine::entity::write::Backing::Entity(properties) => {
+                    turbine::entity::write::remove(properties, #index, index)
+                }
+                turbine::entity::write::Backing::Owned(properties) => {
+                    turbine::entity::write::remove_item(#values_mut, index)
+                }
+            };
+
+            Ok(removed)
+        }
+    }
+}
+
+fn generate_property(
+    base: &BaseUrl,
+    property @ Property {
+        name,
+        type_,
+        kind,
+        required,
+        items,
+        ..
+    }: &Property,
+) -> TokenStream {
+    let index = base.as_str();
+
+    let set = format_ident!("set_{name}");
+    let set_doc = format!("Set `{name}`, the value is validated before it is written.");
+
+    let (value, is_valid) = match kind {
+        PropertyKind::Array => {
+            let count = items
+                .generate_is_valid(&quote!(values.len()))
+                .map(|is_valid| quote!(#is_valid &&));
+
+            (
+                quote!(Vec<#type_>),
+                quote!(|value| value
+                    .as_array()
+                    .is_some_and(|values| #count values.iter().all(<#type_>::is_valid_value))),
+            )
+        }
+        PropertyKind::Plain | PropertyKind::Boxed => {
+            (quote!(#type_), quote!(<#type_>::is_valid_value))
+        }
+    };
+
+    // representation of `value` in the owned properties, see `shared::generate_property`
+    let mut owned = match kind {
+        PropertyKind::Array | PropertyKind::Plain => quote!(value),
+        PropertyKind::Boxed => quote!(Box::new(value)),
+    };
+    if property.is_nullable() {
+        owned = quote!(turbine::Nullable::Value(#owned));
+    } else if !required {
+        owned = quote!(Some(#owned));
+    }
+
+    let clear = (!required).then(|| {
+        let clear = format_ident!("clear_{name}");
+        let doc = format!("Remove `{name}` from the entity.");
+
+        let absent = if property.is_nullable() {
+            quote!(turbine::Nullable::Absent)
+        } else {
+            quote!(None)
+        };
+
+        quote! {
+            #[doc = #doc]
+            pub fn #clear(&mut self) {
+                match &mut self.properties {
+                    turbine::entity::write::Backing::Entity(properties) => {
+                        properties.remove(#index);
+                    }
+                    turbine::entity::write::Backing::Owned(properties) => properties.#name = #absent,
+                }
+            }
+        }
+    });
+
+    let null = property.is_nullable().then(|| {
+        let null = format_ident!("set_{name}_null");
+        let doc = format!("Set `{name}` to `null`, unlike `clear_{name}` the property is kept.");
+
+        quote! {
+            #[doc = #doc]
+            pub fn #null(&mut self) {
+                match &mut self.properties {
+                    turbine::entity::write::Backing::Entity(properties) => {
+                        properties.insert(String::from(#index), serde_json::Value::Null);
+                    }
+                    turbine::entity::write::Backing::Owned(properties) => {
+                        properties.#name = turbine::Nullable::Null;
+                    }
+                }
+            }
+        }
+    });
+
+    let array = matches!(kind, PropertyKind::Array).then(|| generate_array(base, property));
+
+    quote! {
+        #[doc = #set_doc]
+        ///
+        /// # Errors
+        ///
+        /// if the value is not valid for the property type
+        pub fn #set(&mut self, value: #value) -> Result<(), GenericEntityError> {
+            let encoded = turbine::entity::write::encode(#index, &value, #is_valid)?;
+            match &mut self.properties {
+                turbine::entity::write::Backing::Entity(properties) => {
+                    properties.insert(String::from(#index), encoded);
+                }
+                turbine::entity::write::Backing::Owned(properties) => properties.#name = #owned,
+            }
+
+            Ok(())
+        }
+
+        #clear
+
+        #null
+
+        #array
+    }
+}
+
+/// Generate the setters of the `Mut` type, which write validated values into its backing.
+///
+/// The `Mut` type holds the properties of the entity, instead of references into their values, as
+/// adding or removing a property would invalidate them. `PropertiesMut` is therefore only derived
+/// on demand, for as long as it is borrowed.
+pub(super) fn generate(
+    location: &Location,
+    properties: &BTreeMap<&BaseUrl, Property>,
+) -> TokenStream {
+    let name_mut = Ident::new(&location.name_mut.value, Span::call_site());
+
+    let setters = properties
+        .iter()
+        .map(|(base, property)| generate_property(base, property));
+
+    quote! {
+        impl<'a> #name_mut<'a> {
+            /// Borrow the properties mutably, until the next write.
+            ///
+            /// # Errors
+            ///
+            /// if the properties are not valid for the entity type
+            pub fn properties_mut(&mut self) -> Result<PropertiesMut<'_>, GenericEntityError> {
+                match &mut self.properties {
+                    turbine::entity::write::Backing::Entity(properties) => {
+                        PropertiesMut::try_from_value(properties)
+                    }
+                    turbine::entity::write::Backing::Owned(properties) => Ok(properties.as_mut()),
+                }
+            }
+
+            #(#setters)*
+        }
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use type_system::{url::VersionedUrl, EntityType, ValueOrArray};
+
+use crate::{
+    analysis::EdgeKind,
+    entity::generate_absolute_import,
+    name::{Location, LocationKind, NameResolver, PropertyName},
+    shared::accepts_null,
+    AnyType, UnknownProperties,
+};
+
+/// Conversion from the previous version of an entity type into the next one.
+#[derive(Debug, Clone)]
+enum Conversion {
+    /// Every field of the next version is moved out of the previous version, contains the
+    /// initializers of the fields.
+    From(Vec<TokenStream>),
+    /// Values need to be checked, e.g. because a property has become required.
+    TryFrom,
+}
+
+/// Name of the enum over all versions of an entity type, only present for the latest version if
+/// there are multiple versions.
+pub(super) fn name(location: &Location) -> Option<String> {
+    match &location.kind {
+        LocationKind::Latest { other } if !other.is_empty() => {
+            Some(format!("Any{}Version", location.name.value))
+        }
+        _ => None,
+    }
+}
+
+fn entity_type<'a>(resolver: &NameResolver<'a>, url: &VersionedUrl) -> Option<&'a EntityType> {
+    match resolver.get(url) {
+        Some(AnyType::Entity(entity)) => Some(entity),
+        _ => None,
+    }
+}
+
+fn previous<'a>(entity: &EntityType, resolver: &NameResolver<'a>) -> Option<&'a EntityType> {
+    resolver
+        .other_versions_of_url(entity.id())
+        .range(..entity.id().version)
+        .next_back()
+        .and_then(|(_, any)| entity_type(resolver, any.id()))
+}
+
+/// Names of the fields of `entity`.
+fn property_names<'a>(
+    entity: &'a EntityType,
+    resolver: &NameResolver,
+) -> HashMap<&'a VersionedUrl, PropertyName> {
+    // same order as in `entity::generate`, otherwise clashing names might be suffixed differently
+    let property_type_references = entity.property_type_references();
+    let mut references: Vec<_> = property_type_references
+        .iter()
+        .map(|reference| reference.url())
+        .collect();
+    references.sort();
+
+    resolver.property_names(references.iter().copied())
+}
+
+fn is_boxed(entity: &EntityType, url: &VersionedUrl, resolver: &NameResolver) -> bool {
+    resolver.analyzer().edge(entity.id(), url).kind == EdgeKind::Boxed
+}
+
+/// Initializers of the fields of the next version, if every one of them can be moved out of the
+/// previous version, because the property is unchanged or has been added as an optional property.
+///
+/// Every other change, like a widened `oneOf`, results in a different type and needs to be checked
+/// through the wire representation. Removed properties are moved into `extra` by
+/// [`UnknownProperties::Preserve`], which also requires the wire representation.
+fn moves(
+    previous: &EntityType,
+    next: &EntityType,
+    resolver: &NameResolver,
+) -> Option<Vec<TokenStream>> {
+    let is_removed = previous
+        .properties()
+        .keys()
+        .any(|base| !next.properties().contains_key(base));
+    let is_preserved = resolver.unknown_properties() == UnknownProperties::Preserve;
+
+    if is_removed && is_preserved {
+        return None;
+    }
+
+    let previous_names = property_names(previous, resolver);
+    let next_names = property_names(next, resolver);
+
+    let mut properties: Vec<_> = next.properties().iter().collect();
+    properties.sort_by_key(|(base, _)| *base);
+
+    let mut fields = properties
+        .into_iter()
+        .map(|(base, value)| {
+            let url = match value {
+                ValueOrArray::Value(value) => value.url(),
+                ValueOrArray::Array(value) => value.items().url(),
+            };
+
+            let name = Ident::new(&next_names[url].0, Span::call_site());
+            let required = next.required().contains(base);
+            let nullable = matches!(value, ValueOrArray::Value(_)) && accepts_null(url, resolver);
+
+            let Some(previous_value) = previous.properties().get(base) else {
+                return match (required, nullable) {
+                    (true, _) => None,
+                    (false, true) => Some(quote!(#name: turbine::Nullable::Absent)),
+                    (false, false) => Some(quote!(#name: None)),
+                };
+            };
+
+            if previous_value != value
+                || is_boxed(previous, url, resolver) != is_boxed(next, url, resolver)
+            {
+                return None;
+            }
+
+            let previous_name = Ident::new(&previous_names[url].0, Span::call_site());
+            let field = quote!(value.properties.#previous_name);
+
+            match (previous.required().contains(base), required) {
+                (false, true) => None,
+                (true, false) if nullable => {
+                    Some(quote!(#name: turbine::Nullable::Value(#field)))
+                }
+                (true, false) => Some(quote!(#name: Some(#field))),
+                (false, false) | (true, true) => Some(quote!(#name: #field)),
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    if is_preserved {
+        fields.push(quote!(extra: value.properties.extra));
+    }
+
+    Some(fields)
+}
+
+/// Determine how the previous version can be converted into the next version.
+///
+/// `From` is only possible if every field can be moved, see [`moves`], otherwise the conversion
+/// goes through the wire representation and is checked at runtime. Conversions between link and
+/// non-link entity types are not possible.
+fn conversion(
+    previous: &EntityType,
+    next: &EntityType,
+    resolver: &NameResolver,
+) -> Option<Conversion> {
+    let links = resolver.facts().links();
+    if links.contains(previous.id()) != links.contains(next.id()) {
+        return None;
+    }
+
+    Some(moves(previous, next, resolver).map_or(Conversion::TryFrom, Conversion::From))
+}
+
+/// Generate the conversion from the previous version of the entity type, if there is one.
+///
+/// `From` moves the fields, `TryFrom` goes through the wire representation, in both cases
+/// properties which are unknown to the next version are dropped. The metadata is kept, but refers
+/// to the next version of the entity type.
+pub(super) fn generate_conversion(
+    entity: &EntityType,
+    location: &Location,
+    resolver: &NameResolver,
+    is_link: bool,
+) -> TokenStream {
+    let Some(previous) = previous(entity, resolver) else {
+        return quote!();
+    };
+
+    let Some(conversion) = conversion(previous, entity, resolver) else {
+        return quote!();
+    };
+
+    let name = Ident::new(&location.name.value, Span::call_site());
+    let previous = generate_absolute_import(&resolver.location(previous.id()));
+
+    let link_data = is_link.then(|| quote!(link_data: value.link_data,));
+
+    match conversion {
+        Conversion::From(fields) => {
+            let properties = if fields.is_empty() {
+                quote!(Properties)
+            } else {
+                quote!(Properties { #(#fields),* })
+            };
+
+            quote! {
+                impl From<#previous> for #name {
+                    fn from(value: #previous) -> Self {
+                        let mut metadata = value.metadata;
+                        metadata.entity_type_ids.replace(<#previous as TypeUrl>::ID, Self::ID.into_owned());
+
+                        Self {
+                            properties: #properties,
+                            #link_data
+                            metadata,
+                        }
+                    }
+                }
+            }
+        }
+        Conversion::TryFrom => quote! {
+            impl TryFrom<#previous> for #name {
+                type Error = Report<GenericEntityError>;
+
+                fn try_from(value: #previous) -> core::result::Result<Self, Self::Error> {
+                    let turbine::entity::EntityProperties(properties) =
+                        EntityProperties::to_entity_properties(&value)
+                            .change_context(GenericEntityError::Version)?;
+
+                    let mut metadata = value.metadata;
+                    metadata.entity_type_ids.replace(<#previous as TypeUrl>::ID, Self::ID.into_owned());
+
+                    Ok(Self {
+                        properties: Properties::try_from_value(properties)?,
+                        #link_data
+                        metadata,
+                    })
+                }
+            }
+        },
+    }
+}
+
+/// Generate the enum over all versions of the entity type, which dispatches on the version of an
+/// entity and is able to upgrade every version to the latest one.
+pub(super) fn generate_any_version(
+    entity: &EntityType,
+    location: &Location,
+    resolver: &NameResolver,
+) -> TokenStream {
+    let (Some(any), LocationKind::Latest { other }) = (name(location), &location.kind) else {
+        return quote!();
+    };
+
+    let mut versions: Vec<_> = other
+        .iter()
+        .filter_map(|url| entity_type(resolver, url))
+        .chain([entity])
+        .collect();
+    versions.sort_by_key(|entity| entity.id().version);
+
+    let any = Ident::new(&any, Span::call_site());
+    let latest = Ident::new(&location.name.value, Span::call_site());
+
+    let variants: Vec<_> = versions
+        .iter()
+        .map(|entity| format_ident!("V{}", entity.id().version))
+        .collect();
+    let paths: Vec<_> = versions
+        .iter()
+        .map(|entity| generate_absolute_import(&resolver.location(entity.id())))
+        .collect();
+    let numbers: Vec<_> = versions.iter().map(|entity| entity.id().version).collect();
+
+    let upgrade = (0..versions.len()).map(|index| {
+        let variant = &variants[index];
+
+        let steps: Option<Vec<_>> = (index + 1..versions.len())
+            .map(|next| {
+                let path = &paths[next];
+
+                conversion(versions[next - 1], versions[next], resolver).map(|conversion| {
+                    match conversion {
+                        Conversion::From(_) => quote!(let value = <#path>::from(value);),
+                        Conversion::TryFrom => quote! {
+                            let value = match <#path>::try_from(value) {
+                                Ok(value) => value,
+                                Err(error) => return Some(Err(error)),
+                            };
+                        },
+                    }
+                })
+            })
+            .collect();
+
+        steps.map_or_else(
+            || quote!(Self::#variant(_) => None),
+            |steps| {
+                quote! {
+                    Self::#variant(value) => {
+                        #(#steps)*
+                        Some(Ok(value))
+                    }
+                }
+            },
+        )
+    });
+
+    let doc = format!("Any version of [`{latest}`].");
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum #any {
+            #(#variants(#paths)),*
+        }
+
+        impl #any {
+            /// Convert the entity into the version of the entity type, which is specified in its
+            /// metadata, if the entity has multiple versions of the entity type, the first one is
+            /// used.
+            ///
+            /// Returns `None` if the entity is not of any version of the entity type.
+            pub fn from_entity(entity: Entity) -> Option<Result<Self, GenericEntityError>> {
+                let version = entity.metadata.entity_type_ids.iter().find_map(|id| {
+                    match VersionedUrlRef::from(id) {
+                        #(#paths::ID => Some(#numbers),)*
+                        _ => None
+                    }
+                })?;
+
+                match version {
+                    #(#numbers => <#paths as EntityType>::try_from_entity(entity)
+                        .map(|result| result.map(Self::#variants)),)*
+                    _ => None
+                }
+            }
+
+            #[must_use]
+            pub fn version(&self) -> u32 {
+                match self {
+                    #(Self::#variants(_) => #numbers,)*
+                }
+            }
+
+            /// Upgrade the entity to the latest version, by converting between adjacent versions.
+            ///
+            /// Returns `None` if there is no conversion between two of the versions, e.g. because
+            /// one of them is a link, while the other is not.
+            pub fn upgrade(self) -> Option<Result<#latest, GenericEntityError>> {
+                match self {
+                    #(#upgrade,)*
+                }
+            }
+        }
+    }
+}
@@ -5,11 +5,16 @@ mod analysis;
 mod data;
 mod entity;
 mod error;
+#[cfg(test)]
+#[path = "../../turbine/src/fixture.rs"]
+mod fixture;
 mod graph;
 mod name;
+mod path;
 mod property;
 mod shared;
 mod utilities;
+pub mod validate;
 
 use std::{
     cmp::Ordering,
@@ -19,7 +24,7 @@ use std::{
     time::SystemTime,
 };
 
-use error_stack::{Result, ResultExt};
+use error_stack::{Report, Result, ResultExt};
 use quote::__private::TokenStream;
 use thiserror::Error;
 use type_system::{repr, url::VersionedUrl, DataType, EntityType, PropertyType};
@@ -104,6 +109,8 @@ pub enum Error {
     Parse,
     #[error("error while trying to analyze dependencies")]
     DependencyAnalysis,
+    #[error("referenced type has not been provided")]
+    MissingType,
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -121,6 +128,24 @@ pub enum AnyType {
     Entity(EntityType),
 }
 
+impl TryFrom<AnyTypeRepr> for AnyType {
+    type Error = Report<Error>;
+
+    fn try_from(value: AnyTypeRepr) -> core::result::Result<Self, Self::Error> {
+        match value {
+            AnyTypeRepr::Data(data) => DataType::try_from(data)
+                .map(Self::Data)
+                .change_context(Error::Parse),
+            AnyTypeRepr::Property(property) => PropertyType::try_from(property)
+                .map(Self::Property)
+                .change_context(Error::Parse),
+            AnyTypeRepr::Entity(entity) => EntityType::try_from(entity)
+                .map(Self::Entity)
+                .change_context(Error::Parse),
+        }
+    }
+}
+
 impl AnyType {
     const fn id(&self) -> &VersionedUrl {
         match self {
@@ -139,10 +164,25 @@ impl AnyType {
     }
 }
 
+/// How generated entity types handle properties, which are not part of the entity type.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnknownProperties {
+    /// Unknown properties are dropped during conversion.
+    #[default]
+    Drop,
+    /// Unknown properties are kept in an `extra` field and serialized alongside the known
+    /// properties.
+    Preserve,
+    /// Unknown properties are a conversion error.
+    Reject,
+}
+
 pub struct Config {
     pub module: Option<ModuleFlavor>,
     pub overrides: Vec<Override>,
     pub flavors: Vec<Flavor>,
+    pub unknown_properties: UnknownProperties,
     pub timings: bool,
 }
 
@@ -156,20 +196,7 @@ pub struct Output {
 /// if `AnyTypeRepr` is malformed, or an error occurred while generating code
 pub fn process(values: Vec<AnyTypeRepr>, config: Config) -> Result<Output, Error> {
     let now = SystemTime::now();
-    let values: Result<Vec<_>, _> = values
-        .into_iter()
-        .map(|any| match any {
-            AnyTypeRepr::Data(data) => DataType::try_from(data)
-                .map(AnyType::Data)
-                .change_context(Error::Parse),
-            AnyTypeRepr::Property(property) => PropertyType::try_from(property)
-                .map(AnyType::Property)
-                .change_context(Error::Parse),
-            AnyTypeRepr::Entity(entity) => EntityType::try_from(entity)
-                .map(AnyType::Entity)
-                .change_context(Error::Parse),
-        })
-        .collect();
+    let values: Result<Vec<_>, _> = values.into_iter().map(AnyType::try_from).collect();
     if config.timings {
         let elapsed = now.elapsed();
         tracing::info!(?elapsed, "converting types to internal representation");
@@ -196,6 +223,7 @@ pub fn process(values: Vec<AnyTypeRepr>, config: Config) -> Result<Output, Error
     if let Some(module) = config.module {
         names.with_module_flavor(module);
     }
+    names.with_unknown_properties(config.unknown_properties);
 
     let mut files = BTreeMap::new();
     let mut entities = vec![];
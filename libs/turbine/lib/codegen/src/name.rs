@@ -14,7 +14,7 @@ use type_system::url::VersionedUrl;
 
 use crate::{
     analysis::{facts::Facts, DependencyAnalyzer},
-    AnyType,
+    AnyType, UnknownProperties,
 };
 
 #[derive(Debug, Copy, Clone)]
@@ -338,6 +338,7 @@ pub(crate) struct NameResolver<'a> {
     overrides: Vec<Override>,
     module: ModuleFlavor,
     flavors: Vec<Flavor>,
+    unknown_properties: UnknownProperties,
 }
 
 impl<'a> NameResolver<'a> {
@@ -354,6 +355,7 @@ impl<'a> NameResolver<'a> {
             overrides: Vec::new(),
             module: ModuleFlavor::ModRs,
             flavors: Vec::new(),
+            unknown_properties: UnknownProperties::Drop,
         }
     }
 
@@ -369,6 +371,10 @@ impl<'a> NameResolver<'a> {
         self.flavors.push(flavor);
     }
 
+    pub(crate) const fn with_unknown_properties(&mut self, unknown_properties: UnknownProperties) {
+        self.unknown_properties = unknown_properties;
+    }
+
     fn url_into_parts<'b>(&self, url: &'b Url) -> Option<UrlParts<'b>> {
         let flavors = BUILTIN_FLAVORS
             .iter()
@@ -476,7 +482,8 @@ impl<'a> NameResolver<'a> {
         Name { value: name, alias }
     }
 
-    fn other_versions_of_url(&self, url: &VersionedUrl) -> BTreeMap<u32, &'a AnyType> {
+    /// All other versions of the type, which are part of the generated crate, by version.
+    pub(crate) fn other_versions_of_url(&self, url: &VersionedUrl) -> BTreeMap<u32, &'a AnyType> {
         self.lookup
             .iter()
             .filter(|(key, _)| key.base_url == url.base_url)
@@ -669,6 +676,15 @@ impl<'a> NameResolver<'a> {
         output
     }
 
+    /// Returns `true` if the type is part of the generated crate.
+    pub(crate) fn contains(&self, url: &VersionedUrl) -> bool {
+        self.lookup.contains_key(url)
+    }
+
+    pub(crate) fn get(&self, url: &VersionedUrl) -> Option<&'a AnyType> {
+        self.lookup.get(url)
+    }
+
     pub(crate) const fn analyzer(&self) -> &'a DependencyAnalyzer<'a> {
         self.analyzer
     }
@@ -676,6 +692,10 @@ impl<'a> NameResolver<'a> {
     pub(crate) fn facts(&self) -> &'a Facts {
         self.facts
     }
+
+    pub(crate) const fn unknown_properties(&self) -> UnknownProperties {
+        self.unknown_properties
+    }
 }
 
 // TODO: tests
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use type_system::url::BaseUrl;
+
+use crate::{
+    name::Location,
+    shared::{Property, PropertyKind},
+};
+
+pub(crate) fn name(location: &Location) -> String {
+    format!("{}Path", location.name.value)
+}
+
+/// Generate the typed path of a type, including the implementation of `TypeTraverse` for every
+/// variant of the type.
+///
+/// Every property is surfaced as a method, which continues the path at the type of the property,
+/// properties that are arrays need to select an item through `index` before they can be
+/// traversed further.
+pub(crate) fn generate(
+    location: &Location,
+    properties: &BTreeMap<&BaseUrl, Property>,
+) -> TokenStream {
+    let name = Ident::new(&location.name.value, Span::call_site());
+    let name_ref = Ident::new(&location.name_ref.value, Span::call_site());
+    let name_mut = Ident::new(&location.name_mut.value, Span::call_site());
+    let path = Ident::new(&self::name(location), Span::call_site());
+
+    let methods = properties.iter().map(|(base, property)| {
+        let Property {
+            name: method,
+            type_,
+            kind,
+            ..
+        } = property;
+
+        let base = base.as_str();
+        let doc = format!("Continue the path at `{base}`");
+
+        let output = match kind {
+            PropertyKind::Array => quote!(
+                turbine::path::ArrayPath<<#type_ as turbine::TypeTraverse>::Path>
+            ),
+            PropertyKind::Plain | PropertyKind::Boxed => {
+                quote!(<#type_ as turbine::TypeTraverse>::Path)
+            }
+        };
+
+        quote! {
+            #[doc = #doc]
+            #[must_use]
+            pub fn #method(self) -> #output {
+                turbine::path::TypePath::from_segments(self.0.push_field(#base))
+            }
+        }
+    });
+
+    let doc = format!("Typed path into [`{name}`], created through `{name}::path()`.");
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct #path(turbine::path::Segments);
+
+        impl #path {
+            #(#methods)*
+        }
+
+        impl turbine::path::TypePath for #path {
+            fn from_segments(segments: turbine::path::Segments) -> Self {
+                Self(segments)
+            }
+
+            fn path(self) -> turbine::path::Path<'static> {
+                self.0.into_path()
+            }
+        }
+
+        impl turbine::TypeTraverse for #name {
+            type Path = #path;
+        }
+
+        impl turbine::TypeTraverse for #name_ref<'_> {
+            type Path = #path;
+        }
+
+        impl turbine::TypeTraverse for #name_mut<'_> {
+            type Path = #path;
+        }
+    }
+}
@@ -2,18 +2,26 @@ mod inner;
 mod property_value;
 mod type_;
 
-use std::{collections::HashMap, ops::Deref};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Deref,
+};
 
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use type_system::{url::VersionedUrl, DataTypeReference, PropertyType, PropertyTypeReference};
+use type_system::{
+    url::VersionedUrl, DataTypeReference, PropertyType, PropertyTypeReference, PropertyValues,
+    ValueOrArray,
+};
 
 use crate::{
     name::{Location, NameResolver},
+    path,
     property::{
         inner::InnerTypes,
         type_::{Type, TypeGenerator},
     },
+    shared,
     shared::{generate_mod, imports, Import, Variant},
 };
 
@@ -103,7 +111,10 @@ impl<'a> PropertyTypeGenerator<'a> {
         // need to sort, as otherwise results might vary between invocations
         references.sort();
 
+        let path = path::name(&location);
+
         let mut reserved = RESERVED.to_vec();
+        reserved.push(&path);
         reserved.push(&location.name.value);
         reserved.push(&location.name_ref.value);
         reserved.push(&location.name_mut.value);
@@ -174,6 +185,47 @@ impl<'a> PropertyTypeGenerator<'a> {
         generate_mod(&self.location.kind, self.resolver)
     }
 
+    /// Only properties of objects can be traversed, if multiple variants share a property, it is
+    /// only surfaced once.
+    fn path(&self) -> TokenStream {
+        let objects: Vec<_> =
+            self.property
+                .one_of()
+                .iter()
+                .filter_map(|value| match value {
+                    PropertyValues::PropertyTypeObject(object) => Some(object),
+                    PropertyValues::DataTypeReference(_)
+                    | PropertyValues::ArrayOfPropertyValues(_) => None,
+                })
+                .collect();
+
+        let property_names = self.resolver.property_names(
+            objects
+                .iter()
+                .flat_map(|object| object.properties().values())
+                .map(|property| match property {
+                    ValueOrArray::Value(value) => value.url(),
+                    ValueOrArray::Array(value) => value.items().url(),
+                }),
+        );
+
+        let mut properties = BTreeMap::new();
+        for object in objects {
+            for (base, property) in shared::properties(
+                self.property.id(),
+                object.properties(),
+                object.required(),
+                self.resolver,
+                &property_names,
+                &self.locations,
+            ) {
+                properties.entry(base).or_insert(property);
+            }
+        }
+
+        path::generate(&self.location, &properties)
+    }
+
     fn doc(&self) -> TokenStream {
         let property = self.property;
         let title = property.title();
@@ -232,9 +284,7 @@ impl<'a> PropertyTypeGenerator<'a> {
             #def
 
             impl TypeUrl for #name {
-                // The RFC for `allOf` on property types is still in draft
-                type InheritsFrom = ();
-
+                // The RFC for `allOf` on property types is still in draft, there are no ancestors
                 const ID: VersionedUrlRef<'static>  = url!(#base_url / v / #version);
             }
 
@@ -277,6 +327,7 @@ impl<'a> PropertyTypeGenerator<'a> {
         let Type {
             def,
             impl_try_from_value,
+            impl_try_from_raw,
             impl_conversion,
             ..
         } = self.type_(&name_ref, Variant::Ref);
@@ -286,9 +337,7 @@ impl<'a> PropertyTypeGenerator<'a> {
             #def
 
             impl TypeUrl for #name_ref<'_> {
-                // The RFC for `allOf` on property types is still in draft
-                type InheritsFrom = ();
-
+                // The RFC for `allOf` on property types is still in draft, there are no ancestors
                 const ID: VersionedUrlRef<'static>  = url!(#base_url / v / #version);
             }
 
@@ -304,6 +353,10 @@ impl<'a> PropertyTypeGenerator<'a> {
                 fn try_from_value(value: &'a serde_json::Value) -> Result<Self, Self::Error> {
                     #impl_try_from_value
                 }
+
+                fn try_from_raw(value: &'a serde_json::value::RawValue) -> Result<Self, Self::Error> {
+                    #impl_try_from_raw
+                }
             }
 
             #alias
@@ -337,9 +390,7 @@ impl<'a> PropertyTypeGenerator<'a> {
             #def
 
             impl TypeUrl for #name_mut<'_> {
-                // The RFC for `allOf` on property types is still in draft
-                type InheritsFrom = ();
-
+                // The RFC for `allOf` on property types is still in draft, there are no ancestors
                 const ID: VersionedUrlRef<'static>  = url!(#base_url / v / #version);
             }
 
@@ -368,6 +419,7 @@ impl<'a> PropertyTypeGenerator<'a> {
 
         let use_ = self.use_();
         let mod_ = self.mod_();
+        let path = self.path();
 
         let extra = self.state.extra;
 
@@ -380,6 +432,8 @@ impl<'a> PropertyTypeGenerator<'a> {
             #ref_
             #mut_
 
+            #path
+
             #mod_
         }
     }
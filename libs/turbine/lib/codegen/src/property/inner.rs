@@ -135,6 +135,7 @@ impl<'a> InnerGenerator<'a> {
             lifetime,
             impl_ty,
             impl_try_from_value,
+            impl_try_from_raw,
             impl_is_valid_value,
             impl_conversion,
         } = TypeGenerator {
@@ -161,6 +162,14 @@ impl<'a> InnerGenerator<'a> {
             _ => quote!(),
         };
 
+        let try_from_raw = impl_try_from_raw.map(|impl_try_from_raw| {
+            quote! {
+                fn try_from_raw(value: &'a serde_json::value::RawValue) -> Result<Self, GenericPropertyError> {
+                    #impl_try_from_raw
+                }
+            }
+        });
+
         self.state.extra.push(quote!(
             #def
 
@@ -169,6 +178,8 @@ impl<'a> InnerGenerator<'a> {
                     #impl_try_from_value
                 }
 
+                #try_from_raw
+
                 #is_valid_value
 
                 #impl_conversion
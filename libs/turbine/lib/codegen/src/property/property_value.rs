@@ -12,7 +12,11 @@ use crate::{
     name::{Location, NameResolver, PropertyName},
     property::{inner::InnerGenerator, PathSegment, State},
     shared,
-    shared::{generate_property_object_conversion_body, ConversionFunction, Property, Variant},
+    shared::{
+        generate_property_object_conversion_body, ConversionFunction, ItemCount, Property, Source,
+        Variant,
+    },
+    UnknownProperties,
 };
 
 #[derive(Debug, Copy, Clone)]
@@ -88,6 +92,8 @@ pub(super) struct ConversionBody {
 pub(super) struct PropertyValue {
     pub(super) body: TokenStream,
     pub(super) try_from: TokenStream,
+    /// Only generated for the `Ref` variant
+    pub(super) try_from_raw: Option<TokenStream>,
     pub(super) is_valid_value: TokenStream,
     pub(super) conversion: ConversionBody,
 }
@@ -217,12 +223,24 @@ impl<'a> PropertyValueGenerator<'a> {
         };
 
         let self_type = self.self_type;
-        let try_from = quote!({
-            let value = <#type_name #cast>::try_from_value(value)
-                .change_context(GenericPropertyError::Data);
+        let try_from_source = |source: Source| {
+            let function = source.function();
+            let kind = source.kind();
+
+            quote!({
+                let actual = #kind;
+                let value = <#type_name #cast>::#function(value)
+                    .change_context(GenericPropertyError::Data)
+                    .attach_printable_lazy(|| {
+                        turbine::ErrorLocation::new(<#owned_type_name as TypeUrl>::ID, Some(actual))
+                    });
 
-            value.map(#self_type)
-        });
+                value.map(#self_type)
+            })
+        };
+
+        let try_from = try_from_source(Source::Value);
+        let try_from_raw = (self.variant == Variant::Ref).then(|| try_from_source(Source::Raw));
 
         let is_valid_value = {
             quote! {
@@ -237,6 +255,7 @@ impl<'a> PropertyValueGenerator<'a> {
         PropertyValue {
             body: quote!((#vis #type_name)),
             try_from,
+            try_from_raw,
             is_valid_value,
             conversion,
         }
@@ -259,6 +278,7 @@ impl<'a> PropertyValueGenerator<'a> {
                             &quote!(#ref_),
                             ConversionFunction::AsRef,
                             properties,
+                            false,
                         );
 
                         quote! {
@@ -272,6 +292,7 @@ impl<'a> PropertyValueGenerator<'a> {
                             &quote!(#ref_ #variant),
                             ConversionFunction::AsRef,
                             properties,
+                            false,
                         );
 
                         quote! {
@@ -286,6 +307,7 @@ impl<'a> PropertyValueGenerator<'a> {
                             &quote!(#mut_),
                             ConversionFunction::AsMut,
                             properties,
+                            false,
                         );
 
                         quote! {
@@ -299,6 +321,7 @@ impl<'a> PropertyValueGenerator<'a> {
                             &quote!(#mut_ #variant),
                             ConversionFunction::AsMut,
                             properties,
+                            false,
                         );
 
                         quote! {
@@ -323,6 +346,7 @@ impl<'a> PropertyValueGenerator<'a> {
                                 variant: self.variant,
                             },
                             properties,
+                            false,
                         );
 
                         quote! {
@@ -338,6 +362,7 @@ impl<'a> PropertyValueGenerator<'a> {
                                 variant: self.variant,
                             },
                             properties,
+                            false,
                         );
 
                         quote! {
@@ -373,9 +398,11 @@ impl<'a> PropertyValueGenerator<'a> {
 
         let try_from = shared::generate_properties_try_from_value(
             self.variant,
+            Source::Value,
             &properties,
             &Ident::new("GenericPropertyError", Span::call_site()),
             &self.self_type.to_token_stream(),
+            UnknownProperties::Drop,
         );
 
         let is_valid_value = {
@@ -415,6 +442,31 @@ impl<'a> PropertyValueGenerator<'a> {
             Variant::Ref | Variant::Mut => None,
         };
 
+        let expected = shared::generate_url(self.id);
+
+        let try_from_raw = (self.variant == Variant::Ref).then(|| {
+            let try_from = shared::generate_properties_try_from_value(
+                self.variant,
+                Source::Raw,
+                &properties,
+                &Ident::new("GenericPropertyError", Span::call_site()),
+                &self.self_type.to_token_stream(),
+                UnknownProperties::Drop,
+            );
+
+            quote!('variant: {
+                let actual = turbine::raw::kind(value);
+                let Some(properties) = turbine::raw::object(value) else {
+                    break 'variant Err(
+                        Report::new(GenericPropertyError::ExpectedObject)
+                            .attach_printable(turbine::ErrorLocation::new(#expected, Some(actual)))
+                    )
+                };
+
+                #try_from
+            })
+        });
+
         // TODO: integration tests on example project w/ bootstrapping and such
 
         PropertyValue {
@@ -422,12 +474,17 @@ impl<'a> PropertyValueGenerator<'a> {
                 #(#fields),*
             }),
             try_from: quote!('variant: {
+                let actual = turbine::JsonKind::of(&value);
                 let serde_json::Value::Object(#mutability properties) = value #clone else {
-                    break 'variant Err(Report::new(GenericPropertyError::ExpectedObject))
+                    break 'variant Err(
+                        Report::new(GenericPropertyError::ExpectedObject)
+                            .attach_printable(turbine::ErrorLocation::new(#expected, Some(actual)))
+                    )
                 };
 
                 #try_from
             }),
+            try_from_raw,
             is_valid_value,
             conversion,
         }
@@ -566,26 +623,82 @@ impl<'a> PropertyValueGenerator<'a> {
         };
 
         let self_type = self.self_type;
+        let expected = shared::generate_url(self.id);
+
+        // the number of items is checked before any of the items are converted
+        let items = ItemCount::of(array);
+        let count = items.generate_ensure(&quote!(array.len())).map(|ensure| {
+            quote! {
+                if let Err(error) = #ensure {
+                    break 'array Err(error.change_context(GenericPropertyError::Array));
+                }
+            }
+        });
+        let label = count.is_some().then(|| quote!('array:));
+
         let try_from = quote!({
             match value {
-                serde_json::Value::Array(array) => turbine::fold_iter_reports(
-                    array.into_iter().map(|value| <#inner #lifetime>::try_from_value(value))
+                serde_json::Value::Array(array) => #label {
+                    #count
+
+                    turbine::fold_iter_reports(
+                        array
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, value)| turbine::at_index(<#inner #lifetime>::try_from_value(value), index))
+                    )
+                    #suffix
+                    .map(#self_type)
+                    .change_context(GenericPropertyError::Array)
+                },
+                value => Err(
+                    Report::new(GenericPropertyError::ExpectedArray)
+                        .attach_printable(turbine::ErrorLocation::new(
+                            #expected,
+                            Some(turbine::JsonKind::of(&value))
+                        ))
                 )
-                #suffix
-                .map(#self_type)
-                .change_context(GenericPropertyError::Array),
-                _ => Err(Report::new(GenericPropertyError::ExpectedArray))
             }
         });
 
+        let try_from_raw = (self.variant == Variant::Ref).then(|| {
+            quote!({
+                match turbine::raw::array(value) {
+                    Some(array) => #label {
+                        #count
+
+                        turbine::fold_iter_reports(
+                            array
+                                .into_iter()
+                                .enumerate()
+                                .map(|(index, value)| turbine::at_index(<#inner #lifetime>::try_from_raw(value), index))
+                        )
+                        #suffix
+                        .map(#self_type)
+                        .change_context(GenericPropertyError::Array)
+                    },
+                    None => Err(
+                        Report::new(GenericPropertyError::ExpectedArray)
+                            .attach_printable(turbine::ErrorLocation::new(
+                                #expected,
+                                Some(turbine::raw::kind(value))
+                            ))
+                    )
+                }
+            })
+        });
+
         let is_valid_value = {
             let owned = inner_variants.owned;
+            let count = items
+                .generate_is_valid(&quote!(array.len()))
+                .map(|is_valid| quote!(#is_valid &&));
 
             quote! {
                 {
                     let serde_json::Value::Array(array) = value else { return false; };
 
-                    array.iter().all(#owned::is_valid_value)
+                    #count array.iter().all(#owned::is_valid_value)
                 }
             }
         };
@@ -608,6 +721,7 @@ impl<'a> PropertyValueGenerator<'a> {
         PropertyValue {
             body,
             try_from,
+            try_from_raw,
             is_valid_value,
             conversion,
         }
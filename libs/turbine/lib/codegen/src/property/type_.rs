@@ -13,6 +13,7 @@ use crate::{
         },
         PathSegment, State,
     },
+    shared,
     shared::Variant,
 };
 
@@ -22,6 +23,8 @@ pub(super) struct Type {
 
     pub(super) impl_ty: TokenStream,
     pub(super) impl_try_from_value: TokenStream,
+    /// Only generated for the `Ref` variant
+    pub(super) impl_try_from_raw: Option<TokenStream>,
     pub(super) impl_is_valid_value: TokenStream,
     pub(super) impl_conversion: TokenStream,
 }
@@ -57,6 +60,7 @@ impl<'a> TypeGenerator<'a> {
         let PropertyValue {
             body,
             try_from,
+            try_from_raw,
             is_valid_value,
             conversion,
         } = PropertyValueGenerator {
@@ -117,6 +121,7 @@ impl<'a> TypeGenerator<'a> {
             lifetime,
             impl_ty,
             impl_try_from_value: try_from,
+            impl_try_from_raw: try_from_raw,
             impl_is_valid_value,
             impl_conversion,
         }
@@ -187,7 +192,8 @@ impl<'a> TypeGenerator<'a> {
         // in the enum we could in theory name the variant by the name of the struct, problem here
         // is ofc that we would still need to name the other variants and then we have
         // potential name conflicts... Do we need to box on Ref and Mut self-referential?
-        let (body, try_from_variants, is_valid_value, conversion): (
+        let (body, try_from_variants, try_from_raw_variants, is_valid_value, conversion): (
+            Vec<_>,
             Vec<_>,
             Vec<_>,
             Vec<_>,
@@ -203,6 +209,7 @@ impl<'a> TypeGenerator<'a> {
                 let PropertyValue {
                     body,
                     try_from,
+                    try_from_raw,
                     is_valid_value,
                     conversion,
                 } = PropertyValueGenerator {
@@ -223,6 +230,7 @@ impl<'a> TypeGenerator<'a> {
                         #name #body
                     },
                     try_from,
+                    try_from_raw,
                     is_valid_value,
                     conversion,
                 )
@@ -251,14 +259,44 @@ impl<'a> TypeGenerator<'a> {
                     }
                 });
 
+        let expected = shared::generate_url(self.id);
         let try_from = quote! {
             #(
                 #try_from_tries
             )*
 
-            Err(Report::new(GenericPropertyError::InvalidValue))
+            Err(
+                Report::new(GenericPropertyError::InvalidValue)
+                    .attach_printable(turbine::ErrorLocation::new(#expected, Some(turbine::JsonKind::of(&value))))
+            )
         };
 
+        // raw values are not parsed to select the variant, instead every variant is tried in
+        // order, the first one which accepts the value is selected, like with `is_valid_value`
+        let try_from_raw = (self.variant == Variant::Ref).then(|| {
+            let try_from_raw_tries = try_from_raw_variants.into_iter().map(|try_from_raw| {
+                quote! {
+                    // the closure keeps an early return in a variant from ending the search
+                    let attempt = || -> Result<Self, GenericPropertyError> { #try_from_raw };
+
+                    if let Ok(value) = attempt() {
+                        return Ok(value);
+                    }
+                }
+            });
+
+            quote! {
+                #(
+                    #try_from_raw_tries
+                )*
+
+                Err(
+                    Report::new(GenericPropertyError::InvalidValue)
+                        .attach_printable(turbine::ErrorLocation::new(#expected, Some(turbine::raw::kind(value))))
+                )
+            }
+        });
+
         let is_valid_value = quote! {
             fn is_valid_value(value: &serde_json::Value) -> bool {
                 true #(|| #is_valid_value)*
@@ -281,6 +319,7 @@ impl<'a> TypeGenerator<'a> {
             lifetime,
             impl_ty,
             impl_try_from_value: try_from,
+            impl_try_from_raw: try_from_raw,
             impl_is_valid_value: is_valid_value,
             impl_conversion: self.impl_conversion(&conversion),
         }
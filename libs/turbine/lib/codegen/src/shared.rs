@@ -1,17 +1,18 @@
 use std::collections::{BTreeMap, HashMap};
 
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::{format_ident, quote, ToTokens};
 use syn::{Lifetime, Visibility};
 use type_system::{
     url::{BaseUrl, VersionedUrl},
-    PropertyTypeReference, ValueOrArray,
+    Array, PropertyTypeReference, PropertyValues, ValueOrArray,
 };
 
 use crate::{
     analysis::EdgeKind,
     data,
     name::{Location, LocationKind, NameResolver, PropertyName},
+    AnyType, UnknownProperties,
 };
 
 pub(crate) enum PropertyKind {
@@ -20,6 +21,57 @@ pub(crate) enum PropertyKind {
     Boxed,
 }
 
+/// `minItems` and `maxItems` of an array, both are `None` for values, which are not arrays.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ItemCount {
+    pub(crate) min: Option<usize>,
+    pub(crate) max: Option<usize>,
+}
+
+impl ItemCount {
+    pub(crate) const fn of<T>(array: &Array<T>) -> Self {
+        Self {
+            min: array.min_items(),
+            max: array.max_items(),
+        }
+    }
+
+    const fn is_bounded(self) -> bool {
+        self.min.is_some() || self.max.is_some()
+    }
+
+    fn bounds(self) -> (TokenStream, TokenStream) {
+        let min = self
+            .min
+            .map_or_else(|| quote!(None), |min| quote!(Some(#min)));
+        let max = self
+            .max
+            .map_or_else(|| quote!(None), |max| quote!(Some(#max)));
+
+        (min, max)
+    }
+
+    /// Expression, which is `true` if `len` is within the bounds.
+    ///
+    /// Returns `None` if the array is unbounded.
+    pub(crate) fn generate_is_valid(self, len: &TokenStream) -> Option<TokenStream> {
+        let (min, max) = self.bounds();
+
+        self.is_bounded()
+            .then(|| quote!(turbine::is_valid_item_count(#len, #min, #max)))
+    }
+
+    /// Expression of type `Result<(), ItemCountError>`, which checks `len` against the bounds.
+    ///
+    /// Returns `None` if the array is unbounded.
+    pub(crate) fn generate_ensure(self, len: &TokenStream) -> Option<TokenStream> {
+        let (min, max) = self.bounds();
+
+        self.is_bounded()
+            .then(|| quote!(turbine::ensure_item_count(#len, #min, #max)))
+    }
+}
+
 pub(crate) struct Property {
     pub(crate) name: Ident,
     pub(crate) type_: Ident,
@@ -27,6 +79,30 @@ pub(crate) struct Property {
     pub(crate) kind: PropertyKind,
 
     pub(crate) required: bool,
+    /// The property type accepts `null`, arrays are never nullable.
+    pub(crate) nullable: bool,
+    pub(crate) items: ItemCount,
+}
+
+impl Property {
+    /// Optional properties, which accept `null`, distinguish between a missing value and `null`
+    /// through `turbine::Nullable`.
+    pub(crate) const fn is_nullable(&self) -> bool {
+        !self.required && self.nullable
+    }
+}
+
+/// Returns `true` if one of the values of the property type is a data type of the JSON type
+/// `null`.
+pub(crate) fn accepts_null(url: &VersionedUrl, resolver: &NameResolver) -> bool {
+    let Some(AnyType::Property(property)) = resolver.get(url) else {
+        return false;
+    };
+
+    property.one_of().iter().any(|value| match value {
+        PropertyValues::DataTypeReference(reference) => data::is_null(reference.url(), resolver),
+        PropertyValues::PropertyTypeObject(_) | PropertyValues::ArrayOfPropertyValues(_) => false,
+    })
 }
 
 pub(crate) fn properties<'a>(
@@ -57,6 +133,13 @@ pub(crate) fn properties<'a>(
 
             let required = required.contains(base);
 
+            let nullable = matches!(value, ValueOrArray::Value(_)) && accepts_null(url, resolver);
+
+            let items = match value {
+                ValueOrArray::Value(_) => ItemCount::default(),
+                ValueOrArray::Array(array) => ItemCount::of(array),
+            };
+
             let kind = if matches!(value, ValueOrArray::Array(_)) {
                 PropertyKind::Array
             } else if resolver.analyzer().edge(id, url).kind == EdgeKind::Boxed {
@@ -70,6 +153,8 @@ pub(crate) fn properties<'a>(
                 type_,
                 kind,
                 required,
+                nullable,
+                items,
             })
         })
         .collect()
@@ -185,6 +270,39 @@ impl Variant {
     }
 }
 
+/// Input the `Ref` variant is converted from, either a materialised `serde_json::Value` or a
+/// `RawValue`, which is borrowed from the input buffer.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Source {
+    Value,
+    Raw,
+}
+
+impl Source {
+    pub(crate) fn function(self) -> Ident {
+        match self {
+            Self::Value => Ident::new("try_from_value", Span::call_site()),
+            Self::Raw => Ident::new("try_from_raw", Span::call_site()),
+        }
+    }
+
+    /// `turbine::JsonKind` of the variable `value`
+    pub(crate) fn kind(self) -> TokenStream {
+        match self {
+            Self::Value => quote!(turbine::JsonKind::of(&value)),
+            Self::Raw => quote!(turbine::raw::kind(value)),
+        }
+    }
+
+    /// Pattern shadowing the variable `value` with its items, if it is an array.
+    pub(crate) fn array_pattern(self) -> TokenStream {
+        match self {
+            Self::Value => quote!(serde_json::Value::Array(value) = value),
+            Self::Raw => quote!(Some(value) = turbine::raw::array(value)),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct Import {
     pub(crate) vec: bool,
@@ -255,6 +373,8 @@ pub(crate) fn generate_properties_is_valid_value(
                 type_,
                 kind,
                 required,
+                items,
+                ..
             },
         )| {
             let index = base.as_str();
@@ -277,11 +397,23 @@ pub(crate) fn generate_properties_is_valid_value(
 
             let apply = match kind {
                 PropertyKind::Array => {
+                    let count = items
+                        .generate_is_valid(&quote!(value.len()))
+                        .map(|is_valid| {
+                            quote! {
+                                if !#is_valid {
+                                    return false;
+                                }
+                            }
+                        });
+
                     quote! {
                         let serde_json::Value::Array(value) = value else {
                             return false;
                         };
 
+                        #count
+
                         for value in value {
                             if !<#type_>::is_valid_value(value) {
                                 return false;
@@ -315,29 +447,130 @@ pub(crate) fn generate_properties_is_valid_value(
     }
 }
 
+/// Generate a [`VersionedUrlRef`] for `id`, which is validated at compile time.
+///
+/// [`VersionedUrlRef`]: https://docs.rs/turbine/latest/turbine/struct.VersionedUrlRef.html
+pub(crate) fn generate_url(id: &VersionedUrl) -> TokenStream {
+    let base_url = id.base_url.as_str();
+    let version = id.version;
+
+    quote!(turbine::url!(#base_url / v / #version))
+}
+
+fn generate_keys(properties: &BTreeMap<&BaseUrl, Property>) -> TokenStream {
+    let keys = properties.keys().map(|base| base.as_str());
+
+    quote!(const KEYS: &[&str] = &[#(#keys),*];)
+}
+
+/// Reject every property, which is not one of `properties`, if unknown properties are rejected.
+///
+/// Expects the variable `properties` to be in scope.
+pub(crate) fn generate_unknown_properties_check(
+    unknown: UnknownProperties,
+    source: Source,
+    properties: &BTreeMap<&BaseUrl, Property>,
+) -> Option<TokenStream> {
+    if unknown != UnknownProperties::Reject {
+        return None;
+    }
+
+    let keys = generate_keys(properties);
+    let iter = match source {
+        Source::Value => quote!(properties.keys()),
+        Source::Raw => quote!(properties.properties().keys()),
+    };
+
+    Some(quote! {
+        #keys
+        turbine::entity::extra::ensure_known(KEYS, #iter)?;
+    })
+}
+
+/// Type of the `extra` field, which holds properties that are not part of the object.
+pub(crate) fn generate_extra_type(variant: Variant) -> TokenStream {
+    match variant {
+        Variant::Owned => quote!(turbine::entity::ExtraProperties),
+        Variant::Ref => quote!(turbine::entity::ExtraPropertiesRef<'a>),
+        Variant::Mut => quote!(turbine::entity::ExtraPropertiesMut<'a>),
+    }
+}
+
+fn generate_extra_collect(
+    variant: Variant,
+    source: Source,
+    properties: &BTreeMap<&BaseUrl, Property>,
+) -> TokenStream {
+    let keys = generate_keys(properties);
+
+    match (variant, source) {
+        (Variant::Owned, _) => quote! {
+            #keys
+            let extra = turbine::entity::extra::split_off(KEYS, &mut properties);
+        },
+        (Variant::Ref, Source::Value) => quote! {
+            #keys
+            let extra = turbine::entity::extra::collect_ref(KEYS, properties);
+        },
+        (Variant::Ref, Source::Raw) => quote! {
+            #keys
+            let extra = turbine::entity::extra::collect_raw(KEYS, properties)?;
+        },
+        // known properties and `extra` are split in a single pass, see `generate_split_mut`
+        (Variant::Mut, _) => TokenStream::new(),
+    }
+}
+
+/// Split the properties into known properties and `extra` by iterating over them once, the
+/// mutable borrows are disjoint, unlike when every known property is accessed through the map.
+///
+/// Known properties are accessed as `known[position]`.
+fn generate_split_mut(properties: &BTreeMap<&BaseUrl, Property>) -> TokenStream {
+    let keys = properties.keys().map(|base| base.as_str());
+    let length = Literal::usize_unsuffixed(properties.len());
+
+    quote! {
+        const KEYS: [&str; #length] = [#(#keys),*];
+        let (mut known, extra) = turbine::entity::extra::split_mut(&KEYS, properties);
+    }
+}
+
 pub(crate) fn generate_properties_try_from_value(
     variant: Variant,
+    source: Source,
     properties: &BTreeMap<&BaseUrl, Property>,
     error: &Ident,
     type_: &TokenStream,
+    unknown: UnknownProperties,
 ) -> TokenStream {
     // fundamentally we have 3 phases:
     // 1) get all values (as Result)
     // 2) merge them together using `turbine::fold_tuple_reports`
     // 3) merge all values together
 
+    // with `extra` every mutable borrow is taken in a single pass, instead of through the map
+    let split = variant == Variant::Mut && unknown == UnknownProperties::Preserve;
+
     // makes use of labelled breaks in blocks (introduced in 1.65)
-    let values = properties.iter().map(
+    let values = properties.iter().enumerate().map(
         |(
-            base,
-            Property {
-                name,
-                type_,
-                kind,
-                required,
-            },
+            position,
+            (
+                base,
+                property @ Property {
+                    name,
+                    type_,
+                    kind,
+                    required,
+                    items,
+                    ..
+                },
+            ),
         )| {
             let index = base.as_str();
+            let expected = quote!(<#type_ as turbine::TypeUrl>::ID);
+            let function = source.function();
+            let json_kind = source.kind();
 
             let type_ = match variant {
                 Variant::Owned => type_.to_token_stream(),
@@ -349,6 +582,10 @@ pub(crate) fn generate_properties_try_from_value(
             let access = match variant {
                 Variant::Owned => quote!(let value = properties.remove(#index);),
                 Variant::Ref => quote!(let value = properties.get(#index);),
+                Variant::Mut if split => {
+                    let position = Literal::usize_unsuffixed(position);
+                    quote!(let value = known[#position].take();)
+                }
                 Variant::Mut => quote! {
                     // Note: This is super sketch
                     // SAFETY: We already have &mut access, meaning that no one else has mut access
@@ -374,9 +611,23 @@ pub(crate) fn generate_properties_try_from_value(
             let unwrap = if *required {
                 quote! {
                     let Some(value) = value else {
-                        break 'property Err(Report::new(#error::ExpectedProperty(#index)));
+                        break 'property Err(
+                            Report::new(#error::ExpectedProperty)
+                                .attach_printable(turbine::ErrorLocation::new(#expected, None))
+                        );
                     };
                 }
+            } else if property.is_nullable() {
+                // the value is wrapped in `Nullable<>` and can be missing or null!
+                quote! {
+                    let Some(value) = value else {
+                        break 'property Ok(turbine::Nullable::Absent);
+                    };
+
+                    if #json_kind == turbine::JsonKind::Null {
+                        break 'property Ok(turbine::Nullable::Null);
+                    }
+                }
             } else {
                 // the value is wrapped in `Option<>` and can be missing!
                 // null != missing, therefore can only break out if missing, not if null.
@@ -394,38 +645,61 @@ pub(crate) fn generate_properties_try_from_value(
                         _ => None,
                     };
 
+                    let pattern = source.array_pattern();
+
+                    let count = items.generate_ensure(&quote!(value.len())).map(|ensure| {
+                        quote! {
+                            if let Err(error) = #ensure {
+                                break 'property Err(error.change_context(#error::Property));
+                            }
+                        }
+                    });
+
                     quote! {
-                        let value = if let serde_json::Value::Array(value) = value {
+                        let value = if let #pattern {
+                            #count
+
                             turbine::fold_iter_reports(
-                                value.into_iter().map(|value| <#type_>::try_from_value(value))
+                                value
+                                    .into_iter()
+                                    .enumerate()
+                                    .map(|(position, value)| turbine::at_index(<#type_>::#function(value), position))
                             )
                                 #suffix
-                                .change_context(#error::Property(#index)
+                                .change_context(#error::Property
                             )
                         } else {
-                            Err(Report::new(#error::ExpectedArray(#index)))
+                            Err(
+                                Report::new(#error::ExpectedArray)
+                                    .attach_printable(turbine::ErrorLocation::new(
+                                        #expected,
+                                        Some(#json_kind)
+                                    ))
+                            )
                         };
                     }
                 }
                 PropertyKind::Plain => quote! {
-                    let value = <#type_>::try_from_value(value)
-                        .change_context(#error::Property(#index));
+                    let value = <#type_>::#function(value)
+                        .change_context(#error::Property);
                 },
                 PropertyKind::Boxed => quote! {
-                    let value = <#type_>::try_from_value(value)
+                    let value = <#type_>::#function(value)
                         .map(Box::new)
-                        .change_context(#error::Property(#index));
+                        .change_context(#error::Property);
                 },
             };
 
             let ret = if *required {
                 quote!(value)
+            } else if property.is_nullable() {
+                quote!(value.map(turbine::Nullable::Value))
             } else {
                 quote!(value.map(Some))
             };
 
             quote! {
-                let #name = 'property: {
+                let #name = turbine::at_field('property: {
                     #access
 
                     #unwrap
@@ -433,18 +707,34 @@ pub(crate) fn generate_properties_try_from_value(
                     #apply
 
                     #ret
-                };
+                }, #index);
             }
         },
     );
 
-    let fold = generate_fold(properties);
+    let check = generate_unknown_properties_check(unknown, source, properties);
+    let split = split.then(|| generate_split_mut(properties));
+    let fold = (!properties.is_empty()).then(|| generate_fold(properties));
 
-    let fields = properties
+    let mut fields: Vec<_> = properties
         .values()
-        .map(|Property { name, .. }| name.to_token_stream());
+        .map(|Property { name, .. }| name.to_token_stream())
+        .collect();
+
+    let extra = (unknown == UnknownProperties::Preserve).then(|| {
+        fields.push(quote!(extra));
 
+        generate_extra_collect(variant, source, properties)
+    });
+
+    // `extra` is collected before the values, which might shadow `properties`
     quote! {
+        #check
+
+        #split
+
+        #extra
+
         #(#values)*
 
         #fold
@@ -460,11 +750,12 @@ pub(crate) fn generate_properties_try_from_value(
 
 pub(crate) fn generate_property(
     base: &BaseUrl,
-    Property {
+    property @ Property {
         name,
         type_,
         kind,
         required,
+        ..
     }: &Property,
     variant: Variant,
     visibility: Option<&Visibility>,
@@ -496,7 +787,10 @@ pub(crate) fn generate_property(
 
     let mut skip = None;
 
-    if !required {
+    if property.is_nullable() {
+        skip = Some(quote!(#[serde(skip_serializing_if = "turbine::Nullable::is_absent")]));
+        type_ = quote!(turbine::Nullable<#type_>);
+    } else if !required {
         skip = Some(quote!(#[serde(skip_serializing_if = "Option::is_none")]));
         type_ = quote!(Option<#type_>);
     }
@@ -518,10 +812,13 @@ pub(crate) enum ConversionFunction {
 /// creates the body of the conversion
 ///
 /// This method assumes that all names of the property are in scope!
+///
+/// If `extra` is set, the `extra` field is converted as well and needs to be in scope.
 pub(crate) fn generate_property_object_conversion_body(
     name: &TokenStream,
     func: ConversionFunction,
     properties: &BTreeMap<&BaseUrl, Property>,
+    extra: bool,
 ) -> TokenStream {
     let cast = match func {
         ConversionFunction::IntoOwned { variant } => match variant {
@@ -560,6 +857,7 @@ pub(crate) fn generate_property_object_conversion_body(
              type_,
              kind,
              required,
+             ..
          }| {
             let type_ = if let ConversionFunction::IntoOwned { variant } = func {
                 match variant {
@@ -583,7 +881,8 @@ pub(crate) fn generate_property_object_conversion_body(
             };
 
             if !*required {
-                // value is additionally wrapped in an `Option<T>`
+                // value is additionally wrapped in an `Option<T>` or `Nullable<T>`, which share
+                // the same methods
                 value = quote!(#name #map_as .map(|#name| #value));
             }
 
@@ -591,9 +890,12 @@ pub(crate) fn generate_property_object_conversion_body(
         },
     );
 
+    let extra = extra.then(|| quote!(extra: extra.#call(),));
+
     quote! {
         #name {
-            #(#mapping),*
+            #(#mapping,)*
+            #extra
         }
     }
 }
@@ -1,4 +1,4 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 use type_system::EntityType;
 
@@ -18,15 +18,214 @@ fn generate_find_inherits_from<'a>(
             let ident = quote!(crate #(:: #path)* :: #name);
 
             quote!(
-                #ident::ID => <#ident as TypeUrl>::InheritsFrom::resolve().collect()
+                #ident::ID => <#ident as TypeUrl>::ANCESTORS
             )
         });
 
     quote! {
-        pub fn find_inherits_from(url: turbine::VersionedUrlRef) -> alloc::collections::BTreeSet<turbine::VersionedUrlRef<'static>> {
+        /// All entity types the entity type inherits from, either directly or indirectly.
+        pub fn find_inherits_from(url: turbine::VersionedUrlRef) -> &'static [turbine::VersionedUrlRef<'static>] {
             match url {
                 #(#arms ,)*
-                _ => alloc::collections::BTreeSet::new()
+                _ => &[]
+            }
+        }
+    }
+}
+
+/// A single variant of the `AnyEntity` enums.
+struct Variant {
+    name: Ident,
+    path: TokenStream,
+}
+
+fn variants<'a>(
+    entities: impl IntoIterator<Item = &'a EntityType>,
+    resolver: &NameResolver,
+) -> Vec<Variant> {
+    let mut locations: Vec<_> = resolver
+        .locations(entities.into_iter().map(EntityType::id), &[])
+        .into_iter()
+        .collect();
+
+    // sort by URL, so that the output is stable
+    locations.sort_by_key(|(url, _)| *url);
+
+    locations
+        .into_iter()
+        .map(|(_, location)| {
+            let path = determine_import_path(&location);
+            let name = format_ident!("{}", location.name.value);
+
+            let variant = location
+                .alias
+                .value
+                .as_ref()
+                .map_or_else(|| name.clone(), |alias| format_ident!("{alias}"));
+
+            Variant {
+                name: variant,
+                path: quote!(crate #(:: #path)* :: #name),
+            }
+        })
+        .collect()
+}
+
+#[allow(clippy::too_many_lines)]
+fn generate_any_entity(variants: &[Variant]) -> TokenStream {
+    // an empty enum cannot be matched on through a reference
+    if variants.is_empty() {
+        return quote!();
+    }
+
+    let names: Vec<_> = variants.iter().map(|variant| &variant.name).collect();
+    let paths: Vec<_> = variants.iter().map(|variant| &variant.path).collect();
+
+    quote! {
+        /// Any entity type of this crate.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum AnyEntity {
+            #(#names(#paths)),*
+        }
+
+        /// Any entity type of this crate, borrowed from an [`turbine::entity::Entity`].
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum AnyEntityRef<'a> {
+            #(#names(<#paths as turbine::Type>::Ref<'a>)),*
+        }
+
+        /// Any entity type of this crate, mutably borrowed from an [`turbine::entity::Entity`].
+        #[derive(Debug)]
+        pub enum AnyEntityMut<'a> {
+            #(#names(<#paths as turbine::Type>::Mut<'a>)),*
+        }
+
+        impl AnyEntity {
+            /// The first entity type of the entity, which is part of this crate.
+            fn entity_type(entity: &turbine::entity::Entity) -> Option<turbine::VersionedUrlRef<'static>> {
+                entity.metadata.entity_type_ids.iter().find_map(|id| {
+                    match turbine::VersionedUrlRef::from(id) {
+                        #(#paths::ID => Some(#paths::ID),)*
+                        _ => None
+                    }
+                })
+            }
+
+            /// Convert the entity into the entity type of this crate, which is specified in its
+            /// metadata, if the entity has multiple entity types of this crate, the first one is
+            /// used.
+            ///
+            /// Returns `None` if none of the entity types is part of this crate.
+            pub fn from_entity(
+                entity: turbine::entity::Entity
+            ) -> Option<error_stack::Result<Self, turbine::GenericEntityError>> {
+                match Self::entity_type(&entity)? {
+                    #(#paths::ID => <#paths as turbine::EntityType>::try_from_entity(entity)
+                        .map(|result| result.map(Self::#names)),)*
+                    _ => None
+                }
+            }
+
+            #[must_use]
+            pub fn id(&self) -> turbine::VersionedUrlRef<'static> {
+                match self {
+                    #(Self::#names(_) => #paths::ID,)*
+                }
+            }
+
+            #[must_use]
+            pub fn as_ref(&self) -> AnyEntityRef<'_> {
+                match self {
+                    #(Self::#names(value) => AnyEntityRef::#names(turbine::Type::as_ref(value)),)*
+                }
+            }
+
+            #[must_use]
+            pub fn as_mut(&mut self) -> AnyEntityMut<'_> {
+                match self {
+                    #(Self::#names(value) => AnyEntityMut::#names(turbine::Type::as_mut(value)),)*
+                }
+            }
+        }
+
+        impl<'a> AnyEntityRef<'a> {
+            /// Borrow the entity as the entity type of this crate, which is specified in its
+            /// metadata, if the entity has multiple entity types of this crate, the first one is
+            /// used.
+            ///
+            /// Returns `None` if none of the entity types is part of this crate.
+            pub fn from_entity(
+                entity: &'a turbine::entity::Entity
+            ) -> Option<error_stack::Result<Self, turbine::GenericEntityError>> {
+                Self::from_entity_as(entity, AnyEntity::entity_type(entity)?)
+            }
+
+            /// Borrow the entity as each of its entity types, which are part of this crate.
+            pub fn views(
+                entity: &'a turbine::entity::Entity
+            ) -> alloc::vec::Vec<error_stack::Result<Self, turbine::GenericEntityError>> {
+                entity
+                    .metadata
+                    .entity_type_ids
+                    .iter()
+                    .filter_map(|id| Self::from_entity_as(entity, turbine::VersionedUrlRef::from(id)))
+                    .collect()
+            }
+
+            fn from_entity_as(
+                entity: &'a turbine::entity::Entity,
+                entity_type: turbine::VersionedUrlRef,
+            ) -> Option<error_stack::Result<Self, turbine::GenericEntityError>> {
+                match entity_type {
+                    #(#paths::ID => <<#paths as turbine::Type>::Ref<'a> as turbine::EntityTypeRef<'a>>::try_from_entity(entity)
+                        .map(|result| result.map(Self::#names)),)*
+                    _ => None
+                }
+            }
+
+            #[must_use]
+            pub fn id(&self) -> turbine::VersionedUrlRef<'static> {
+                match self {
+                    #(Self::#names(_) => #paths::ID,)*
+                }
+            }
+
+            #[must_use]
+            pub fn into_owned(self) -> AnyEntity {
+                match self {
+                    #(Self::#names(value) => AnyEntity::#names(turbine::TypeRef::into_owned(value)),)*
+                }
+            }
+        }
+
+        impl<'a> AnyEntityMut<'a> {
+            /// Mutably borrow the entity as the entity type of this crate, which is specified in
+            /// its metadata, if the entity has multiple entity types of this crate, the first one
+            /// is used.
+            ///
+            /// Returns `None` if none of the entity types is part of this crate.
+            pub fn from_entity(
+                entity: &'a mut turbine::entity::Entity
+            ) -> Option<error_stack::Result<Self, turbine::GenericEntityError>> {
+                match AnyEntity::entity_type(entity)? {
+                    #(#paths::ID => <<#paths as turbine::Type>::Mut<'a> as turbine::EntityTypeMut<'a>>::try_from_entity(entity)
+                        .map(|result| result.map(Self::#names)),)*
+                    _ => None
+                }
+            }
+
+            #[must_use]
+            pub fn id(&self) -> turbine::VersionedUrlRef<'static> {
+                match self {
+                    #(Self::#names(_) => #paths::ID,)*
+                }
+            }
+
+            #[must_use]
+            pub fn into_owned(self) -> AnyEntity {
+                match self {
+                    #(Self::#names(value) => AnyEntity::#names(turbine::TypeMut::into_owned(value)),)*
+                }
             }
         }
     }
@@ -36,5 +235,12 @@ pub(crate) fn generate<'a>(
     entities: impl IntoIterator<Item = &'a EntityType> + Clone,
     resolver: &NameResolver,
 ) -> TokenStream {
-    generate_find_inherits_from(entities, resolver)
+    let find_inherits_from = generate_find_inherits_from(entities.clone(), resolver);
+    let any_entity = generate_any_entity(&variants(entities, resolver));
+
+    quote! {
+        #find_inherits_from
+
+        #any_entity
+    }
 }
@@ -0,0 +1,680 @@
+//! Validation of entities against types, which are only known at runtime.
+//!
+//! The validator mirrors the checks of the generated `try_from_entity` and reports the same
+//! errors, including the [`ErrorLocation`] of every value, which failed to validate.
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+};
+
+use error_stack::{Context, Report, Result, ResultExt};
+use serde_json::Value;
+use turbine::{
+    at_field, at_index, ensure_item_count,
+    entity::Entity,
+    fold_iter_reports,
+    types::data::{
+        constraint::{
+            compare, compile_pattern, equals, is_multiple_of, matches_format, matches_pattern,
+            Limit, Regex,
+        },
+        Integer,
+    },
+    BaseUrlRef, ErrorLocation, GenericDataTypeError, GenericEntityError, GenericPropertyError,
+    JsonKind, VersionedUrlRef,
+};
+use type_system::{
+    url::{BaseUrl, VersionedUrl},
+    DataTypeReference, EntityType, Object, PropertyType, PropertyTypeReference, PropertyValues,
+    ValueOrArray,
+};
+
+use crate::{analysis::unify::UnificationAnalyzer, error::ErrorAccumulator, AnyType, Error};
+
+/// Errors, which are used when validating the properties of an object.
+///
+/// The key of the property is not part of the error, but recorded in the [`ErrorLocation`].
+trait PropertiesError: Context {
+    fn expected_property() -> Self;
+    fn expected_array() -> Self;
+    fn property() -> Self;
+}
+
+impl PropertiesError for GenericEntityError {
+    fn expected_property() -> Self {
+        Self::ExpectedProperty
+    }
+
+    fn expected_array() -> Self {
+        Self::ExpectedArray
+    }
+
+    fn property() -> Self {
+        Self::Property
+    }
+}
+
+impl PropertiesError for GenericPropertyError {
+    fn expected_property() -> Self {
+        Self::ExpectedProperty
+    }
+
+    fn expected_array() -> Self {
+        Self::ExpectedArray
+    }
+
+    fn property() -> Self {
+        Self::Property
+    }
+}
+
+/// Validate entities against types, which have been discovered at runtime.
+///
+/// Entity types are unified on creation, meaning that properties of parent types are validated as
+/// well.
+pub struct Validator {
+    types: HashMap<VersionedUrl, AnyType>,
+    links: HashSet<VersionedUrl>,
+    /// `pattern` constraints of all data types, compiled once on creation
+    patterns: HashMap<String, Regex>,
+}
+
+impl Validator {
+    /// Create a new validator, `types` must include every type referenced, including the parents
+    /// of entity types.
+    ///
+    /// # Errors
+    ///
+    /// if a referenced type is missing, or the entity types cannot be unified
+    pub fn new(types: impl IntoIterator<Item = AnyType>) -> Result<Self, Error> {
+        let analyzer = UnificationAnalyzer::new(types);
+        let (types, facts) = analyzer.run().change_context(Error::DependencyAnalysis)?;
+
+        let mut errors = ErrorAccumulator::new();
+        let mut patterns = HashMap::new();
+
+        for any in types.values() {
+            if let AnyType::Data(data) = any {
+                if let Some(Value::String(pattern)) = data.additional_properties().get("pattern") {
+                    // like in the generated code, a pattern which cannot be compiled is skipped
+                    match compile_pattern(pattern) {
+                        Ok(regex) => {
+                            patterns.insert(pattern.clone(), regex);
+                        }
+                        Err(error) => tracing::warn!(
+                            id = %data.id(),
+                            ?error,
+                            "`pattern` of data type cannot be compiled and is not enforced"
+                        ),
+                    }
+                }
+            }
+
+            let references: Vec<&VersionedUrl> = match any {
+                AnyType::Data(_) => vec![],
+                AnyType::Property(property) => property
+                    .property_type_references()
+                    .into_iter()
+                    .map(PropertyTypeReference::url)
+                    .chain(
+                        property
+                            .data_type_references()
+                            .into_iter()
+                            .map(DataTypeReference::url),
+                    )
+                    .collect(),
+                AnyType::Entity(entity) => entity
+                    .property_type_references()
+                    .into_iter()
+                    .map(PropertyTypeReference::url)
+                    .collect(),
+            };
+
+            for url in references {
+                if !types.contains_key(url) {
+                    errors.extend_one(
+                        Report::new(Error::MissingType).attach_printable(url.to_string()),
+                    );
+                }
+            }
+        }
+
+        errors.into_result()?;
+
+        Ok(Self {
+            types,
+            links: facts.links().clone(),
+            patterns,
+        })
+    }
+
+    /// Validate the entity against each of its entity types, entity types which are unknown to the
+    /// validator are skipped.
+    ///
+    /// Returns `None` if none of the entity types is known to the validator.
+    ///
+    /// # Errors
+    ///
+    /// if the entity does not adhere to one of the entity types, the error is the same as the one
+    /// of the generated `try_from_entity`
+    #[must_use]
+    pub fn validate(&self, entity: &Entity) -> Option<Result<(), GenericEntityError>> {
+        let entity_types: Vec<_> = entity
+            .metadata
+            .entity_type_ids
+            .iter()
+            .filter_map(|entity_type_id| {
+                let id = VersionedUrl {
+                    base_url: BaseUrl::new(entity_type_id.base_url.as_str().to_owned()).ok()?,
+                    version: entity_type_id.version,
+                };
+
+                match self.types.get(&id) {
+                    Some(AnyType::Entity(entity_type)) => Some(entity_type),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        if entity_types.is_empty() {
+            return None;
+        }
+
+        let mut errors = ErrorAccumulator::new();
+        for entity_type in entity_types {
+            errors.push(self.entity(entity_type, entity));
+        }
+
+        Some(errors.into_result())
+    }
+
+    fn entity(&self, entity_type: &EntityType, entity: &Entity) -> Result<(), GenericEntityError> {
+        let mut errors = ErrorAccumulator::new();
+
+        errors.push(
+            self.properties(entity_type.properties(), entity_type.required(), |key| {
+                entity.properties.properties().get(key)
+            }),
+        );
+
+        if self.links.contains(entity_type.id()) && entity.link_data.is_none() {
+            errors.extend_one(Report::new(GenericEntityError::ExpectedLinkData));
+        }
+
+        errors.into_result()
+    }
+
+    /// `lookup` abstracts over the map type, as entities use a different one than `serde_json`.
+    fn properties<'v, C: PropertiesError>(
+        &self,
+        schema: &HashMap<BaseUrl, ValueOrArray<PropertyTypeReference>>,
+        required: &[BaseUrl],
+        lookup: impl Fn(&str) -> Option<&'v Value>,
+    ) -> Result<(), C> {
+        let mut errors = ErrorAccumulator::new();
+
+        // sorted, so that the errors are reported in the same order as the generated code
+        let schema: BTreeMap<_, _> = schema.iter().collect();
+
+        for (base, reference) in schema {
+            let url = match reference {
+                ValueOrArray::Value(value) => value.url(),
+                ValueOrArray::Array(value) => value.items().url(),
+            };
+
+            let result = match (lookup(base.as_str()), reference) {
+                (None, _) if required.contains(base) => Err(Report::new(C::expected_property())
+                    .attach_printable(ErrorLocation::new(url_ref(url), None))),
+                (None, _) => Ok(()),
+                (Some(value), ValueOrArray::Value(_)) => {
+                    self.property_type(url, value).change_context(C::property())
+                }
+                // the number of items is checked before any of the items, like in the generated
+                // code
+                (Some(Value::Array(values)), ValueOrArray::Array(array)) => {
+                    ensure_item_count(values.len(), array.min_items(), array.max_items())
+                        .change_context(C::property())
+                        .and_then(|()| {
+                            fold_iter_reports(values.iter().enumerate().map(|(index, value)| {
+                                at_index(self.property_type(url, value), index)
+                            }))
+                            .map(|_| ())
+                            .change_context(C::property())
+                        })
+                }
+                (Some(value), ValueOrArray::Array(_)) => Err(Report::new(C::expected_array())
+                    .attach_printable(ErrorLocation::new(url_ref(url), Some(JsonKind::of(value))))),
+            };
+
+            errors.push(at_field(result, base.as_str().to_owned()));
+        }
+
+        errors.into_result()
+    }
+
+    fn property_type(&self, url: &VersionedUrl, value: &Value) -> Result<(), GenericPropertyError> {
+        let Some(AnyType::Property(property)) = self.types.get(url) else {
+            // every reference has been checked on creation
+            return Err(Report::new(GenericPropertyError::InvalidValue)
+                .attach_printable(ErrorLocation::new(url_ref(url), Some(JsonKind::of(value)))));
+        };
+
+        self.one_of(property, property.one_of(), value)
+    }
+
+    /// Mirrors the generated code, if there is only a single variant, its error is returned
+    /// directly, otherwise the first matching variant is used.
+    fn one_of(
+        &self,
+        property: &PropertyType,
+        values: &[PropertyValues],
+        value: &Value,
+    ) -> Result<(), GenericPropertyError> {
+        if let [schema] = values {
+            return self.property_value(property, schema, value);
+        }
+
+        if values
+            .iter()
+            .any(|schema| self.property_value(property, schema, value).is_ok())
+        {
+            return Ok(());
+        }
+
+        Err(
+            Report::new(GenericPropertyError::InvalidValue).attach_printable(ErrorLocation::new(
+                url_ref(property.id()),
+                Some(JsonKind::of(value)),
+            )),
+        )
+    }
+
+    fn property_value(
+        &self,
+        property: &PropertyType,
+        schema: &PropertyValues,
+        value: &Value,
+    ) -> Result<(), GenericPropertyError> {
+        match schema {
+            PropertyValues::DataTypeReference(reference) => self
+                .data_type(reference.url(), value)
+                .change_context(GenericPropertyError::Data)
+                .attach_printable(ErrorLocation::new(
+                    url_ref(reference.url()),
+                    Some(JsonKind::of(value)),
+                )),
+            PropertyValues::PropertyTypeObject(object) => self.object(property, object, value),
+            PropertyValues::ArrayOfPropertyValues(array) => {
+                let Value::Array(values) = value else {
+                    return Err(
+                        Report::new(GenericPropertyError::ExpectedArray).attach_printable(
+                            ErrorLocation::new(url_ref(property.id()), Some(JsonKind::of(value))),
+                        ),
+                    );
+                };
+
+                ensure_item_count(values.len(), array.min_items(), array.max_items())
+                    .change_context(GenericPropertyError::Array)?;
+
+                fold_iter_reports(values.iter().enumerate().map(|(index, value)| {
+                    at_index(self.one_of(property, array.items().one_of(), value), index)
+                }))
+                .map(|_| ())
+                .change_context(GenericPropertyError::Array)
+            }
+        }
+    }
+
+    fn object(
+        &self,
+        property: &PropertyType,
+        object: &Object<ValueOrArray<PropertyTypeReference>, 1>,
+        value: &Value,
+    ) -> Result<(), GenericPropertyError> {
+        let Value::Object(properties) = value else {
+            return Err(
+                Report::new(GenericPropertyError::ExpectedObject).attach_printable(
+                    ErrorLocation::new(url_ref(property.id()), Some(JsonKind::of(value))),
+                ),
+            );
+        };
+
+        self.properties(object.properties(), object.required(), |key| {
+            properties.get(key)
+        })
+    }
+
+    fn data_type(&self, url: &VersionedUrl, value: &Value) -> Result<(), GenericDataTypeError> {
+        let Some(AnyType::Data(data)) = self.types.get(url) else {
+            return Err(Report::new(GenericDataTypeError::Base));
+        };
+
+        if !is_of_type(data.json_type(), value) {
+            return Err(Report::new(GenericDataTypeError::Base));
+        }
+
+        // sorted, so that the first violated constraint is the same as the generated code
+        let constraints: BTreeMap<_, _> = data.additional_properties().iter().collect();
+
+        for (keyword, constraint) in constraints {
+            if let Some(name) = violated_constraint(keyword, constraint, value, &self.patterns) {
+                return Err(Report::new(GenericDataTypeError::Constraint(name)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Borrow `url` as the URL type of turbine.
+///
+/// turbine depends on a different revision of the type-system crate than codegen, its
+/// `VersionedUrl` is therefore a distinct type and the URL is converted through its string
+/// representation.
+fn url_ref(url: &VersionedUrl) -> VersionedUrlRef<'_> {
+    VersionedUrlRef::new_unchecked(
+        BaseUrlRef::new_unchecked(url.base_url.as_str()),
+        url.version,
+    )
+}
+
+fn is_of_type(json_type: &str, value: &Value) -> bool {
+    match json_type {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => <Integer as turbine::DataType>::is_valid_value(value),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        _ => true,
+    }
+}
+
+/// Returns the name of the constraint if `value` violates it, unknown keywords are ignored.
+fn violated_constraint(
+    keyword: &str,
+    constraint: &Value,
+    value: &Value,
+    patterns: &HashMap<String, Regex>,
+) -> Option<&'static str> {
+    let length = value.as_str().map(|value| value.chars().count() as u64);
+    let number = match value {
+        Value::Number(number) => Some(number),
+        _ => None,
+    };
+    let bound = |limit: &serde_json::Number, ordering: fn(Ordering) -> bool| {
+        number
+            .zip(Limit::from_number(limit))
+            .map(|(number, limit)| compare(number, limit).is_some_and(ordering))
+    };
+
+    let (name, satisfied) = match (keyword, constraint) {
+        ("minLength", Value::Number(limit)) => (
+            "minLength",
+            length
+                .zip(limit.as_u64())
+                .map(|(length, limit)| length >= limit),
+        ),
+        ("maxLength", Value::Number(limit)) => (
+            "maxLength",
+            length
+                .zip(limit.as_u64())
+                .map(|(length, limit)| length <= limit),
+        ),
+        ("pattern", Value::String(pattern)) => (
+            "pattern",
+            value
+                .as_str()
+                .zip(patterns.get(pattern))
+                .map(|(value, regex)| matches_pattern(regex, value)),
+        ),
+        ("format", Value::String(format)) => (
+            "format",
+            value.as_str().map(|value| matches_format(format, value)),
+        ),
+        ("minimum", Value::Number(limit)) => ("minimum", bound(limit, Ordering::is_ge)),
+        ("maximum", Value::Number(limit)) => ("maximum", bound(limit, Ordering::is_le)),
+        ("exclusiveMinimum", Value::Number(limit)) => {
+            ("exclusiveMinimum", bound(limit, Ordering::is_gt))
+        }
+        ("exclusiveMaximum", Value::Number(limit)) => {
+            ("exclusiveMaximum", bound(limit, Ordering::is_lt))
+        }
+        ("multipleOf", Value::Number(limit)) => (
+            "multipleOf",
+            number
+                .zip(Limit::from_number(limit))
+                .map(|(number, limit)| is_multiple_of(number, limit)),
+        ),
+        ("const", constant) => ("const", Some(equals(value, constant))),
+        ("enum", Value::Array(variants)) if !variants.is_empty() => (
+            "enum",
+            Some(variants.iter().any(|variant| equals(value, variant))),
+        ),
+        _ => return None,
+    };
+
+    (satisfied == Some(false)).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{fixture, AnyTypeRepr};
+
+    fn validator() -> Validator {
+        let types: Vec<AnyTypeRepr> = serde_json::from_value(json!([
+            {
+                "$id": "http://localhost:3000/@alice/types/entity-type/country/v/1",
+                "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/entity-type",
+                "allOf": [],
+                "description": null,
+                "kind": "entityType",
+                "links": {},
+                "properties": {
+                    "http://localhost:3000/@alice/types/property-type/name/": {
+                        "$ref": "http://localhost:3000/@alice/types/property-type/name/v/1"
+                    },
+                    "http://localhost:3000/@alice/types/property-type/alias/": {
+                        "type": "array",
+                        "items": {
+                            "$ref": "http://localhost:3000/@alice/types/property-type/alias/v/1"
+                        },
+                        "minItems": 1,
+                        "maxItems": 2
+                    }
+                },
+                "required": [
+                    "http://localhost:3000/@alice/types/property-type/name/"
+                ],
+                "title": "Country",
+                "type": "object"
+            },
+            {
+                "$id": "http://localhost:3000/@alice/types/property-type/name/v/1",
+                "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/property-type",
+                "description": null,
+                "kind": "propertyType",
+                "oneOf": [
+                    {
+                        "$ref": "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1"
+                    }
+                ],
+                "title": "Name"
+            },
+            {
+                "$id": "http://localhost:3000/@alice/types/property-type/alias/v/1",
+                "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/property-type",
+                "description": null,
+                "kind": "propertyType",
+                "oneOf": [
+                    {
+                        "$ref": "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1"
+                    }
+                ],
+                "title": "Alias"
+            },
+            {
+                "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/data-type",
+                "kind": "dataType",
+                "$id": "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1",
+                "title": "Text",
+                "description": "An ordered sequence of characters",
+                "type": "string"
+            }
+        ]))
+        .expect("should be valid types");
+
+        let types = types
+            .into_iter()
+            .map(AnyType::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("should be able to convert types");
+
+        Validator::new(types).expect("should be able to create validator")
+    }
+
+    fn entity(properties: Value) -> Entity {
+        fixture::entity()
+            .entity_type("http://localhost:3000/@alice/types/entity-type/country/v/1")
+            .properties(properties)
+            .build()
+    }
+
+    #[test]
+    fn valid() {
+        let validator = validator();
+        let entity = entity(json!({
+            "http://localhost:3000/@alice/types/property-type/name/": "Germany"
+        }));
+
+        validator
+            .validate(&entity)
+            .expect("entity type should be known")
+            .expect("entity should be valid");
+    }
+
+    #[test]
+    fn invalid() {
+        let validator = validator();
+        let entity = entity(json!({
+            "http://localhost:3000/@alice/types/property-type/name/": 42
+        }));
+
+        let report = validator
+            .validate(&entity)
+            .expect("entity type should be known")
+            .expect_err("entity should be invalid");
+
+        let location = report
+            .downcast_ref::<ErrorLocation>()
+            .expect("should have a location");
+
+        assert_eq!(location.actual, Some(JsonKind::Number));
+        assert_eq!(location.path.len(), 1);
+    }
+
+    #[test]
+    fn missing() {
+        let validator = validator();
+        let entity = entity(json!({}));
+
+        let report = validator
+            .validate(&entity)
+            .expect("entity type should be known")
+            .expect_err("entity should be invalid");
+
+        assert!(matches!(
+            report.current_context(),
+            GenericEntityError::ExpectedProperty
+        ));
+    }
+
+    #[test]
+    fn items() {
+        let validator = validator();
+
+        let valid = entity(json!({
+            "http://localhost:3000/@alice/types/property-type/name/": "Germany",
+            "http://localhost:3000/@alice/types/property-type/alias/": ["Deutschland"]
+        }));
+
+        validator
+            .validate(&valid)
+            .expect("entity type should be known")
+            .expect("entity should be valid");
+
+        let empty = entity(json!({
+            "http://localhost:3000/@alice/types/property-type/name/": "Germany",
+            "http://localhost:3000/@alice/types/property-type/alias/": []
+        }));
+
+        let report = validator
+            .validate(&empty)
+            .expect("entity type should be known")
+            .expect_err("entity should be invalid");
+
+        assert_eq!(
+            report.downcast_ref::<turbine::ItemCountError>(),
+            Some(&turbine::ItemCountError::TooFew {
+                expected: 1,
+                actual: 0
+            })
+        );
+    }
+
+    #[test]
+    fn constraints() {
+        let patterns = HashMap::from([(
+            "^[a-z]+$".to_owned(),
+            compile_pattern("^[a-z]+$").expect("should be a valid pattern"),
+        )]);
+
+        assert!(is_of_type("integer", &json!(1.0)));
+        assert!(!is_of_type("integer", &json!(1.5)));
+
+        let violated = |keyword, constraint: Value, value: Value| {
+            violated_constraint(keyword, &constraint, &value, &patterns)
+        };
+
+        assert_eq!(violated("pattern", json!("^[a-z]+$"), json!("abc")), None);
+        assert_eq!(
+            violated("pattern", json!("^[a-z]+$"), json!("ABC")),
+            Some("pattern")
+        );
+
+        assert_eq!(
+            violated("maximum", json!(u64::MAX - 1), json!(u64::MAX)),
+            Some("maximum")
+        );
+        assert_eq!(violated("minimum", json!(1), json!(1.0)), None);
+        assert_eq!(violated("const", json!(1), json!(1.0)), None);
+        assert_eq!(violated("enum", json!([1, 2]), json!(3)), Some("enum"));
+    }
+
+    #[test]
+    fn unsupported_pattern() {
+        let types: Vec<AnyTypeRepr> = serde_json::from_value(json!([{
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/data-type",
+            "kind": "dataType",
+            "$id": "http://localhost:3000/@alice/types/data-type/handle/v/1",
+            "title": "Handle",
+            "type": "string",
+            "pattern": "^(?!admin)[a-z]+$"
+        }]))
+        .expect("should be valid types");
+
+        let types = types
+            .into_iter()
+            .map(AnyType::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("should be able to convert types");
+
+        let validator = Validator::new(types).expect("lookarounds should not be enforced");
+        assert!(validator.patterns.is_empty());
+    }
+}
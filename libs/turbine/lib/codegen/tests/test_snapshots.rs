@@ -7,7 +7,7 @@ use std::{
     time::SystemTime,
 };
 
-use codegen::Config;
+use codegen::{Config, UnknownProperties};
 use similar_asserts::assert_eq;
 
 #[test]
@@ -39,6 +39,19 @@ fn snapshots() {
         .map_or(false, |mode| mode.to_ascii_lowercase() == "overwrite");
 
     for path in snapshots {
+        // the handling of unknown properties is selected through the suffix of the snapshot name
+        let stem = path
+            .file_stem()
+            .map(std::ffi::OsStr::to_string_lossy)
+            .unwrap_or_default();
+        let unknown_properties = if stem.ends_with("-preserve") {
+            UnknownProperties::Preserve
+        } else if stem.ends_with("-reject") {
+            UnknownProperties::Reject
+        } else {
+            UnknownProperties::default()
+        };
+
         let snapshot = fs::read_to_string(&path).expect("unable to read snapshot");
         let contents = serde_json::from_str(&snapshot).expect("snapshot is invalid JSON");
 
@@ -48,6 +61,7 @@ fn snapshots() {
             module: None,
             overrides: vec![],
             flavors: vec![],
+            unknown_properties,
         })
         .expect("able to generate valid rust");
         println!("Elapsed: {:?}", now.elapsed().unwrap());
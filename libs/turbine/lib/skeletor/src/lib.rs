@@ -9,7 +9,7 @@ use std::{
     process::Command,
 };
 
-use codegen::{AnyTypeRepr, Flavor, ModuleFlavor, Output, Override};
+use codegen::{AnyTypeRepr, Flavor, ModuleFlavor, Output, Override, UnknownProperties};
 use error_stack::{Result, ResultExt};
 use onlyerror::Error;
 use pathdiff::diff_paths;
@@ -94,6 +94,7 @@ pub struct Config {
 
     pub overrides: Vec<Override>,
     pub flavors: Vec<Flavor>,
+    pub unknown_properties: UnknownProperties,
 
     pub force: bool,
     pub timings: bool,
@@ -150,6 +151,7 @@ pub fn generate(types: Vec<AnyTypeRepr>, mut config: Config) -> Result<(), Error
         module: Some(config.style.into()),
         overrides: config.overrides,
         flavors: config.flavors,
+        unknown_properties: config.unknown_properties,
         timings: config.timings,
     })
     .change_context(Error::Codegen)?;
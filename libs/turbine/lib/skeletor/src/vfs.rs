@@ -171,7 +171,6 @@ impl VirtualFolder {
             #![allow(unused_mut)]
 
             use turbine::TypeUrl;
-            use turbine::TypeHierarchyResolution as _;
 
             #utilities
 
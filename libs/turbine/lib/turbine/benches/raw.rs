@@ -0,0 +1,107 @@
+//! Parsing throughput of a subgraph dump, materialised as [`Entity`] versus borrowed as
+//! [`RawEntity`].
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde_json::{json, Value};
+use turbine::{
+    entity::Entity,
+    raw::RawEntity,
+    types::data::{Number, Text},
+    DataTypeRef, Type,
+};
+
+#[path = "../src/fixture.rs"]
+mod fixture;
+
+type TextRef<'a> = <Text as Type>::Ref<'a>;
+type NumberRef<'a> = <Number as Type>::Ref<'a>;
+
+const NAME: &str = "https://example.com/property-type/name/";
+const DESCRIPTION: &str = "https://example.com/property-type/description/";
+const AGE: &str = "https://example.com/property-type/age/";
+
+fn entity(index: usize) -> Value {
+    fixture::entity()
+        .entity_types(&[fixture::PERSON])
+        .properties(json!({
+            NAME: format!("Entity {index}"),
+            DESCRIPTION: "A moderately long description, which is repeated for every entity in \
+                          the dump, to resemble the text properties of a real subgraph.",
+            AGE: index,
+        }))
+        .into_value()
+}
+
+fn dump(entities: usize) -> Vec<u8> {
+    let entities: Vec<_> = (0..entities).map(entity).collect();
+
+    serde_json::to_vec(&entities).expect("should be serializable")
+}
+
+fn read_entities(input: &[u8]) -> usize {
+    let entities: Vec<Entity> = serde_json::from_slice(input).expect("should be valid entities");
+
+    entities
+        .iter()
+        .map(|entity| {
+            let properties = entity.properties.properties();
+
+            let name = TextRef::try_from_value(&properties[NAME]).expect("should be text");
+            let description =
+                TextRef::try_from_value(&properties[DESCRIPTION]).expect("should be text");
+            let age = NumberRef::try_from_value(&properties[AGE]).expect("should be a number");
+
+            name.len() + description.len() + usize::from(age.as_u64().is_some())
+        })
+        .sum()
+}
+
+fn read_raw_entities(input: &[u8]) -> usize {
+    let entities: Vec<RawEntity> = serde_json::from_slice(input).expect("should be valid entities");
+
+    entities
+        .iter()
+        .map(|entity| {
+            let properties = &entity.properties;
+
+            let name = TextRef::try_from_raw(properties.get(NAME).expect("should exist"))
+                .expect("should be text");
+            let description =
+                TextRef::try_from_raw(properties.get(DESCRIPTION).expect("should exist"))
+                    .expect("should be text");
+            let age = NumberRef::try_from_raw(properties.get(AGE).expect("should exist"))
+                .expect("should be a number");
+
+            name.len() + description.len() + usize::from(age.as_u64().is_some())
+        })
+        .sum()
+}
+
+fn parse(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("parse");
+
+    for entities in [100, 10_000] {
+        let input = dump(entities);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("entity", entities),
+            &input,
+            |bencher, input| {
+                bencher.iter(|| read_entities(black_box(input)));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("raw_entity", entities),
+            &input,
+            |bencher, input| {
+                bencher.iter(|| read_raw_entities(black_box(input)));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -0,0 +1,19 @@
+//! Markers used by the builders emitted by code generation.
+//!
+//! Every required property of a builder is tracked in a type parameter, which starts out as
+//! [`Missing`] and becomes [`Set`] once the property has been provided. `build` is only
+//! implemented once every type parameter is [`Set`].
+
+/// Required value, which has not been provided yet.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Missing;
+
+/// Required value, which has been provided.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Set<T>(pub T);
+
+impl<T> Set<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
@@ -1,33 +1,54 @@
+pub mod extra;
 pub mod interval;
+pub mod wire;
+pub mod write;
 
 use alloc::{
     collections::{btree_map::OccupiedEntry, BTreeMap},
     string::{String, ToString},
+    vec,
+    vec::Vec,
 };
 use core::fmt;
 
+use error_stack::Report;
+pub use extra::{ExtraProperties, ExtraPropertiesMut, ExtraPropertiesRef};
 use hashbrown::HashMap;
 pub use interval::{
     ClosedTemporalBound, Interval, LeftClosedTemporalInterval, OpenTemporalBound, Timestamp,
 };
 use serde::{
-    de::{value::StrDeserializer, Error},
+    de::{
+        value::{SeqAccessDeserializer, StrDeserializer},
+        Error, SeqAccess, Visitor,
+    },
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use serde_json::Value;
+use serde_json::{Number, Value};
 use time::OffsetDateTime;
 use type_system::url::VersionedUrl;
 use uuid::Uuid;
 
+use crate::{OptionalEntityLink, TypeUrl, VersionedUrlRef, WireError};
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct EntityId {
     pub owned_by_id: Uuid,
     pub entity_uuid: Uuid,
+    /// Set if the entity is a draft, newer versions of the Graph API append it as a third
+    /// component.
+    pub draft_id: Option<Uuid>,
 }
 
 impl fmt::Display for EntityId {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(fmt, "{}~{}", self.owned_by_id, self.entity_uuid)
+        write!(fmt, "{}~{}", self.owned_by_id, self.entity_uuid)?;
+
+        if let Some(draft_id) = self.draft_id {
+            write!(fmt, "~{draft_id}")?;
+        }
+
+        Ok(())
     }
 }
 
@@ -47,6 +68,10 @@ impl<'de> Deserialize<'de> for EntityId {
             entity_uuid: Uuid::deserialize(StrDeserializer::new(parts.next().ok_or_else(
                 || D::Error::custom("failed to find second component of `~` delimited string"),
             )?))?,
+            draft_id: parts
+                .next()
+                .map(|part| Uuid::deserialize(StrDeserializer::new(part)))
+                .transpose()?,
         })
     }
 }
@@ -60,18 +85,51 @@ impl Serialize for EntityId {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase", from = "wire::v2::ProvenanceMetadata")]
 pub struct ProvenanceMetadata {
     pub record_created_by_id: Uuid,
     pub record_archived_by_id: Option<Uuid>,
+    /// Provenance fields, which are unknown to this version of turbine.
+    #[serde(flatten)]
+    pub additional: BTreeMap<String, Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
-#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
 pub struct EntityTemporalMetadata {
     pub decision_time: LeftClosedTemporalInterval,
     pub transaction_time: LeftClosedTemporalInterval,
+    /// Temporal fields, which are unknown to this version of turbine, e.g. the pinned axis.
+    #[serde(flatten)]
+    pub additional: BTreeMap<String, Value>,
+}
+
+impl EntityTemporalMetadata {
+    /// Returns `true` if the edition was valid at the given decision and transaction time.
+    #[must_use]
+    pub fn contains(&self, decision_time: Timestamp, transaction_time: Timestamp) -> bool {
+        self.decision_time.contains_timestamp(decision_time)
+            && self.transaction_time.contains_timestamp(transaction_time)
+    }
+
+    /// Returns `true` if both editions overlap on both time axes.
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.decision_time.overlaps(&other.decision_time)
+            && self.transaction_time.overlaps(&other.transaction_time)
+    }
+
+    /// Returns the time span in which both editions are valid, or `None` if they do not overlap
+    /// on either of the time axes.
+    #[must_use]
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        Some(Self {
+            decision_time: self.decision_time.intersect(&other.decision_time)?,
+            transaction_time: self.transaction_time.intersect(&other.transaction_time)?,
+            additional: BTreeMap::new(),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
@@ -82,7 +140,6 @@ pub struct EntityRecordId {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
 pub struct EntityLinkOrder {
     #[serde(default, rename = "leftToRightOrder")]
     pub left_to_right: Option<i32>,
@@ -90,13 +147,16 @@ pub struct EntityLinkOrder {
     pub right_to_left: Option<i32>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct LinkData {
     pub left_entity_id: EntityId,
     pub right_entity_id: EntityId,
     #[serde(flatten)]
     pub order: EntityLinkOrder,
+    /// Link fields, which are unknown to this version of turbine, e.g. the confidence of the link.
+    #[serde(flatten)]
+    pub additional: BTreeMap<String, Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
@@ -112,6 +172,22 @@ impl EntityProperties {
     pub fn properties_mut(&mut self) -> &mut HashMap<String, Value> {
         &mut self.0
     }
+
+    /// Serialize typed properties into their wire representation, which is keyed by base URL.
+    ///
+    /// # Errors
+    ///
+    /// if the properties cannot be serialized, or do not serialize into an object
+    pub fn from_typed<T: Serialize>(value: &T) -> Result<Self, Report<WireError>> {
+        let value = serde_json::to_value(value)
+            .map_err(|error| Report::new(WireError::Serialize).attach_printable(error))?;
+
+        let Value::Object(properties) = value else {
+            return Err(Report::new(WireError::ExpectedObject));
+        };
+
+        Ok(Self(properties.into_iter().collect()))
+    }
 }
 
 impl From<HashMap<String, Value>> for EntityProperties {
@@ -120,24 +196,247 @@ impl From<HashMap<String, Value>> for EntityProperties {
     }
 }
 
+/// Entity types of an entity, every entity has at least one entity type and every entity type is
+/// only present once.
+///
+/// Deserializes from either a single entity type or a list of entity types.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntityTypeIds(Vec<VersionedUrl>);
+
+impl EntityTypeIds {
+    #[must_use]
+    pub fn new(entity_type_id: VersionedUrl) -> Self {
+        Self(vec![entity_type_id])
+    }
+
+    /// Add an entity type, returns `false` if the entity already has the entity type.
+    pub fn insert(&mut self, entity_type_id: VersionedUrl) -> bool {
+        if self.0.contains(&entity_type_id) {
+            return false;
+        }
+
+        self.0.push(entity_type_id);
+        true
+    }
+
+    /// Replace the entity type `previous` with `next`, e.g. after the entity has been converted to
+    /// another version of the entity type.
+    pub fn replace(&mut self, previous: VersionedUrlRef, next: VersionedUrl) {
+        let Some(index) = self.0.iter().position(|other| previous == *other) else {
+            self.insert(next);
+            return;
+        };
+
+        if self.0.contains(&next) {
+            self.0.remove(index);
+        } else {
+            self.0[index] = next;
+        }
+    }
+
+    /// The first entity type of the entity, for entities with a single entity type this is the
+    /// only one.
+    #[must_use]
+    pub fn first(&self) -> &VersionedUrl {
+        &self.0[0]
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, VersionedUrl> {
+        self.0.iter()
+    }
+
+    #[must_use]
+    pub fn contains(&self, entity_type_id: VersionedUrlRef) -> bool {
+        self.0.iter().any(|other| entity_type_id == *other)
+    }
+
+    /// Returns `true` if one of the entity types is `entity_type_id` or inherits from it.
+    ///
+    /// `ancestors` returns all ancestors of an entity type, e.g. the `find_inherits_from` function
+    /// of a generated crate.
+    #[must_use]
+    pub fn is_instance_of(
+        &self,
+        entity_type_id: VersionedUrlRef,
+        ancestors: impl Fn(VersionedUrlRef) -> &'static [VersionedUrlRef<'static>],
+    ) -> bool {
+        self.0
+            .iter()
+            .map(VersionedUrlRef::from)
+            .any(|other| other == entity_type_id || ancestors(other).contains(&entity_type_id))
+    }
+}
+
+impl<'de> Deserialize<'de> for EntityTypeIds {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct EntityTypeIdsVisitor;
+
+        impl<'de> Visitor<'de> for EntityTypeIdsVisitor {
+            type Value = EntityTypeIds;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an entity type id or a non-empty list of entity type ids")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                VersionedUrl::deserialize(StrDeserializer::new(value)).map(EntityTypeIds::new)
+            }
+
+            fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut ids =
+                    Vec::<VersionedUrl>::deserialize(SeqAccessDeserializer::new(seq))?.into_iter();
+
+                let mut output = EntityTypeIds::new(
+                    ids.next()
+                        .ok_or_else(|| A::Error::invalid_length(0, &self))?,
+                );
+                for id in ids {
+                    output.insert(id);
+                }
+
+                Ok(output)
+            }
+        }
+
+        deserializer.deserialize_any(EntityTypeIdsVisitor)
+    }
+}
+
+impl<'a> IntoIterator for &'a EntityTypeIds {
+    type IntoIter = core::slice::Iter<'a, VersionedUrl>;
+    type Item = &'a VersionedUrl;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<VersionedUrl> for EntityTypeIds {
+    fn from(value: VersionedUrl) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Metadata of an entity, independent of the version of the Graph API it has been received from.
+///
+/// Deserializes from any of the versions in [`wire`] and serializes into the latest version.
 #[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", from = "wire::v2::EntityMetadata")]
 pub struct EntityMetadata {
     pub record_id: EntityRecordId,
     pub temporal_versioning: EntityTemporalMetadata,
-    pub entity_type_id: VersionedUrl,
+    pub entity_type_ids: EntityTypeIds,
     pub provenance: ProvenanceMetadata,
     pub archived: bool,
     pub draft: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Number>,
+    /// Per-property metadata, like provenance and confidence, keyed by the property.
+    #[serde(rename = "properties", skip_serializing_if = "Option::is_none")]
+    pub property_metadata: Option<Value>,
+    /// Metadata fields, which are unknown to this version of turbine.
+    #[serde(flatten)]
+    pub additional: BTreeMap<String, Value>,
 }
 
+/// Fields which are unknown to this version of turbine are kept in `additional`, unknown metadata
+/// is kept in [`EntityMetadata`].
 #[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
-#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
 pub struct Entity {
     pub properties: EntityProperties,
     #[serde(default)]
     pub link_data: Option<LinkData>,
     pub metadata: EntityMetadata,
+    /// Fields, which are unknown to this version of turbine.
+    #[serde(flatten)]
+    pub additional: BTreeMap<String, Value>,
+}
+
+impl Entity {
+    /// Create a new entity from a typed value, the properties are serialized into their wire
+    /// representation.
+    ///
+    /// # Errors
+    ///
+    /// if the properties of `value` cannot be serialized
+    pub fn from_typed<T>(value: &T, metadata: EntityMetadata) -> Result<Self, Report<WireError>>
+    where
+        T: crate::EntityProperties + OptionalEntityLink,
+    {
+        Ok(Self {
+            properties: value.to_entity_properties()?,
+            link_data: value.link_data_opt().cloned(),
+            metadata,
+            additional: BTreeMap::new(),
+        })
+    }
+
+    /// Write the properties and link data of a typed value back into the entity.
+    ///
+    /// Properties which are unknown to `T` are preserved, while properties known to `T`, but not
+    /// set on `value` are removed.
+    ///
+    /// # Errors
+    ///
+    /// if the properties of `value` cannot be serialized, in that case the entity is left
+    /// untouched
+    pub fn apply<T>(&mut self, value: &T) -> Result<(), Report<WireError>>
+    where
+        T: crate::EntityProperties + OptionalEntityLink,
+    {
+        let EntityProperties(properties) = value.to_entity_properties()?;
+
+        let current = self.properties.properties_mut();
+        for key in T::KEYS {
+            current.remove(*key);
+        }
+        current.extend(properties);
+
+        if let Some(link_data) = value.link_data_opt() {
+            self.link_data = Some(link_data.clone());
+        }
+
+        Ok(())
+    }
+}
+
+/// Payload to create a new entity, as produced by the generated builders.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct CreateEntity {
+    pub properties: EntityProperties,
+    pub entity_type_id: VersionedUrl,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_data: Option<LinkData>,
+}
+
+impl CreateEntity {
+    /// Create the payload from a typed value, the properties are serialized into their wire
+    /// representation.
+    ///
+    /// # Errors
+    ///
+    /// if the properties of `value` cannot be serialized
+    pub fn from_typed<T>(value: &T) -> Result<Self, Report<WireError>>
+    where
+        T: crate::EntityProperties + OptionalEntityLink + TypeUrl,
+    {
+        Ok(Self {
+            properties: value.to_entity_properties()?,
+            entity_type_id: T::ID.into_owned(),
+            link_data: value.link_data_opt().cloned(),
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Ord, PartialOrd)]
@@ -155,6 +454,12 @@ impl RevisionId {
     }
 }
 
+impl From<OffsetDateTime> for RevisionId {
+    fn from(time: OffsetDateTime) -> Self {
+        Self(time)
+    }
+}
+
 // This isn't super efficient, but by far the easiest way to implement serialization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct Inner<T> {
@@ -202,7 +507,7 @@ impl From<EntityVertex> for EntityVertexInner {
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 #[serde(from = "EntityVertexInner", into = "EntityVertexInner")]
-pub struct EntityVertex(BTreeMap<RevisionId, Entity>);
+pub struct EntityVertex(pub(crate) BTreeMap<RevisionId, Entity>);
 
 impl EntityVertex {
     #[must_use]
@@ -240,12 +545,469 @@ impl EntityVertex {
     }
 
     #[must_use]
-    pub fn entity_type_id(&self) -> &VersionedUrl {
-        &self.latest().metadata.entity_type_id
+    pub fn entity_type_ids(&self) -> &EntityTypeIds {
+        &self.latest().metadata.entity_type_ids
     }
 
     #[must_use]
     pub fn entity_id(&self) -> EntityId {
         self.latest().metadata.record_id.entity_id
     }
+
+    /// Returns the edition, which was valid at the given decision time, as it is known now.
+    ///
+    /// The transaction time is pinned to now, use [`Self::as_of`] to query a previous state of
+    /// knowledge.
+    #[must_use]
+    pub fn as_of_decision_time(&self, timestamp: Timestamp) -> Option<&Entity> {
+        self.as_of(timestamp, Timestamp::now())
+    }
+
+    /// Returns the edition, which is valid now, as it was known at the given transaction time.
+    ///
+    /// The decision time is pinned to now, use [`Self::as_of`] to query a different decision time.
+    #[must_use]
+    pub fn as_of_transaction_time(&self, timestamp: Timestamp) -> Option<&Entity> {
+        self.as_of(Timestamp::now(), timestamp)
+    }
+
+    /// Returns the edition, which was valid at the given decision time, as it was known at the
+    /// given transaction time.
+    #[must_use]
+    pub fn as_of(&self, decision_time: Timestamp, transaction_time: Timestamp) -> Option<&Entity> {
+        self.0.values().rev().find(|entity| {
+            entity
+                .metadata
+                .temporal_versioning
+                .contains(decision_time, transaction_time)
+        })
+    }
+
+    /// Iterate over all editions, which were valid at any point during the given decision time
+    /// interval, in the order of their revision.
+    pub fn editions_in_decision_time(
+        &self,
+        interval: LeftClosedTemporalInterval,
+    ) -> impl Iterator<Item = &Entity> + '_ {
+        self.0.values().filter(move |entity| {
+            entity
+                .metadata
+                .temporal_versioning
+                .decision_time
+                .overlaps(&interval)
+        })
+    }
+
+    /// Iterate over all editions, which were known at any point during the given transaction time
+    /// interval, in the order of their revision.
+    pub fn editions_in_transaction_time(
+        &self,
+        interval: LeftClosedTemporalInterval,
+    ) -> impl Iterator<Item = &Entity> + '_ {
+        self.0.values().filter(move |entity| {
+            entity
+                .metadata
+                .temporal_versioning
+                .transaction_time
+                .overlaps(&interval)
+        })
+    }
+
+    /// Iterate over all editions, which overlap with the given intervals on both time axes, in
+    /// the order of their revision.
+    pub fn editions_in(
+        &self,
+        decision_time: LeftClosedTemporalInterval,
+        transaction_time: LeftClosedTemporalInterval,
+    ) -> impl Iterator<Item = &Entity> + '_ {
+        self.0.values().filter(move |entity| {
+            let temporal_versioning = &entity.metadata.temporal_versioning;
+
+            temporal_versioning.decision_time.overlaps(&decision_time)
+                && temporal_versioning
+                    .transaction_time
+                    .overlaps(&transaction_time)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use serde::Serialize;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::fixture;
+
+    #[derive(Serialize)]
+    struct Properties {
+        #[serde(rename = "https://example.com/property-type/name/")]
+        name: String,
+        #[serde(
+            rename = "https://example.com/property-type/age/",
+            skip_serializing_if = "Option::is_none"
+        )]
+        age: Option<u32>,
+    }
+
+    struct Person {
+        properties: Properties,
+    }
+
+    impl crate::EntityProperties for Person {
+        type Properties = Properties;
+
+        const KEYS: &'static [&'static str] = &[
+            "https://example.com/property-type/name/",
+            "https://example.com/property-type/age/",
+        ];
+
+        fn properties(&self) -> &Self::Properties {
+            &self.properties
+        }
+    }
+
+    impl OptionalEntityLink for Person {
+        fn link_data_opt(&self) -> Option<&LinkData> {
+            None
+        }
+    }
+
+    impl crate::TypeUrl for Person {
+        const ID: crate::VersionedUrlRef<'static> =
+            crate::url!("https://example.com/entity-type/person/" / v / 1);
+    }
+
+    #[test]
+    fn apply_preserves_unknown_properties() {
+        let mut entity: Entity = fixture::entity()
+            .properties(json!({
+                "https://example.com/property-type/name/": "Alice",
+                "https://example.com/property-type/age/": 42,
+                "https://example.com/property-type/email/": "alice@example.com"
+            }))
+            .build();
+
+        let person = Person {
+            properties: Properties {
+                name: String::from("Bob"),
+                age: None,
+            },
+        };
+
+        entity.apply(&person).expect("should be able to apply");
+
+        let expected = entity_properties(json!({
+            "https://example.com/property-type/name/": "Bob",
+            "https://example.com/property-type/email/": "alice@example.com"
+        }));
+
+        assert_eq!(entity.properties, expected);
+    }
+
+    #[test]
+    fn from_typed() {
+        let person = Person {
+            properties: Properties {
+                name: String::from("Alice"),
+                age: Some(42),
+            },
+        };
+
+        let template: Entity = fixture::entity().build();
+        let entity =
+            Entity::from_typed(&person, template.metadata).expect("should be able to serialize");
+
+        let expected = entity_properties(json!({
+            "https://example.com/property-type/name/": "Alice",
+            "https://example.com/property-type/age/": 42
+        }));
+
+        assert_eq!(entity.properties, expected);
+        assert_eq!(entity.link_data, None);
+    }
+
+    #[test]
+    fn create_entity_from_typed() {
+        let person = Person {
+            properties: Properties {
+                name: String::from("Alice"),
+                age: None,
+            },
+        };
+
+        let payload = CreateEntity::from_typed(&person).expect("should be able to serialize");
+
+        assert_eq!(
+            serde_json::to_value(&payload).expect("should be able to serialize"),
+            json!({
+                "properties": {
+                    "https://example.com/property-type/name/": "Alice"
+                },
+                "entityTypeId": "https://example.com/entity-type/person/v/1"
+            })
+        );
+    }
+
+    struct Record(EntityMetadata);
+
+    impl crate::EntityRecord for Record {
+        fn metadata(&self) -> &EntityMetadata {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn record() {
+        use crate::EntityRecord as _;
+
+        let record = Record(fixture::entity().build::<Entity>().metadata);
+
+        assert_eq!(record.entity_id().to_string(), fixture::ENTITY_ID);
+        assert_eq!(record.edition_id().to_string(), fixture::EDITION_ID);
+        assert_eq!(record.created_by().to_string(), fixture::ACTOR_ID);
+        assert_eq!(record.archived_by(), None);
+        assert!(!record.is_archived());
+        assert!(!record.is_draft());
+    }
+
+    #[test]
+    fn entity_type_ids() {
+        let person = Person::ID.into_owned();
+        let agent = crate::url!("https://example.com/entity-type/agent/" / v / 1);
+        let employee = crate::url!("https://example.com/entity-type/employee/" / v / 1);
+
+        let entity: Entity = fixture::entity().build();
+        assert_eq!(entity.metadata.entity_type_ids.first(), &person);
+
+        let ids: EntityTypeIds = serde_json::from_value(json!([
+            "https://example.com/entity-type/person/v/1",
+            "https://example.com/entity-type/employee/v/1",
+            "https://example.com/entity-type/person/v/1"
+        ]))
+        .expect("should be valid entity type ids");
+
+        assert_eq!(ids.iter().count(), 2);
+        assert!(ids.contains(employee));
+        assert!(!ids.contains(agent));
+
+        let ancestors = |id: VersionedUrlRef| -> &'static [VersionedUrlRef<'static>] {
+            if id == Person::ID {
+                &[crate::url!(
+                    "https://example.com/entity-type/agent/" / v / 1
+                )]
+            } else {
+                &[]
+            }
+        };
+
+        assert!(ids.is_instance_of(agent, ancestors));
+        assert!(ids.is_instance_of(employee, ancestors));
+        assert!(!EntityTypeIds::new(person).is_instance_of(employee, ancestors));
+
+        serde_json::from_value::<EntityTypeIds>(json!([]))
+            .expect_err("should require at least one entity type");
+    }
+
+    fn entity_properties(value: Value) -> EntityProperties {
+        serde_json::from_value(value).expect("should be valid properties")
+    }
+
+    fn timestamp(seconds: i64) -> Timestamp {
+        Timestamp::from(
+            OffsetDateTime::from_unix_timestamp(seconds).expect("should be a valid timestamp"),
+        )
+    }
+
+    fn edition(
+        decision_time: LeftClosedTemporalInterval,
+        transaction_time: LeftClosedTemporalInterval,
+        name: &str,
+    ) -> (RevisionId, Entity) {
+        let mut entity: Entity = fixture::entity()
+            .properties(json!({ "https://example.com/property-type/name/": name }))
+            .build();
+
+        let temporal_versioning = &mut entity.metadata.temporal_versioning;
+        temporal_versioning.decision_time = decision_time;
+        temporal_versioning.transaction_time = transaction_time;
+
+        (RevisionId(transaction_time.start.timestamp().time), entity)
+    }
+
+    fn name(entity: Option<&Entity>) -> Option<&str> {
+        entity?
+            .properties
+            .properties()
+            .get("https://example.com/property-type/name/")?
+            .as_str()
+    }
+
+    /// Creates the following history:
+    ///
+    /// - at transaction time 10 "Alice" is recorded as valid since decision time 0
+    /// - at transaction time 20 "Bob" is recorded as valid since decision time 15, which supersedes
+    ///   the knowledge about "Alice" from decision time 15 onwards
+    fn vertex() -> EntityVertex {
+        EntityVertex(BTreeMap::from([
+            edition(
+                LeftClosedTemporalInterval::since(timestamp(0)),
+                LeftClosedTemporalInterval::between(timestamp(10), timestamp(20)),
+                "Alice",
+            ),
+            edition(
+                LeftClosedTemporalInterval::between(timestamp(0), timestamp(15)),
+                LeftClosedTemporalInterval::since(timestamp(20)),
+                "Alice",
+            ),
+            edition(
+                LeftClosedTemporalInterval::since(timestamp(15)),
+                LeftClosedTemporalInterval::since(timestamp(21)),
+                "Bob",
+            ),
+        ]))
+    }
+
+    #[test]
+    fn as_of_decision_time() {
+        let vertex = vertex();
+
+        assert_eq!(
+            name(vertex.as_of_decision_time(timestamp(5))),
+            Some("Alice")
+        );
+        assert_eq!(name(vertex.as_of_decision_time(timestamp(15))), Some("Bob"));
+    }
+
+    #[test]
+    fn as_of_transaction_time() {
+        let vertex = vertex();
+
+        assert_eq!(name(vertex.as_of_transaction_time(timestamp(5))), None);
+        assert_eq!(
+            name(vertex.as_of_transaction_time(timestamp(15))),
+            Some("Alice")
+        );
+        assert_eq!(
+            name(vertex.as_of_transaction_time(timestamp(25))),
+            Some("Bob")
+        );
+    }
+
+    #[test]
+    fn as_of() {
+        let vertex = vertex();
+
+        assert_eq!(
+            name(vertex.as_of(timestamp(16), timestamp(15))),
+            Some("Alice")
+        );
+        assert_eq!(
+            name(vertex.as_of(timestamp(16), timestamp(25))),
+            Some("Bob")
+        );
+        assert_eq!(
+            name(vertex.as_of(timestamp(5), timestamp(25))),
+            Some("Alice")
+        );
+        assert_eq!(name(vertex.as_of(timestamp(5), timestamp(5))), None);
+    }
+
+    #[test]
+    fn as_of_corrected_edition() {
+        // at transaction time 20 the entity was corrected to only exist since decision time 5
+        let vertex = EntityVertex(BTreeMap::from([
+            edition(
+                LeftClosedTemporalInterval::since(timestamp(0)),
+                LeftClosedTemporalInterval::between(timestamp(10), timestamp(20)),
+                "Alice",
+            ),
+            edition(
+                LeftClosedTemporalInterval::since(timestamp(5)),
+                LeftClosedTemporalInterval::since(timestamp(20)),
+                "Bob",
+            ),
+        ]));
+
+        // the superseded edition is no longer part of the current knowledge
+        assert_eq!(name(vertex.as_of_decision_time(timestamp(2))), None);
+        assert_eq!(name(vertex.as_of_decision_time(timestamp(7))), Some("Bob"));
+
+        assert_eq!(
+            name(vertex.as_of_transaction_time(timestamp(15))),
+            Some("Alice")
+        );
+        assert_eq!(
+            name(vertex.as_of_transaction_time(timestamp(25))),
+            Some("Bob")
+        );
+
+        assert_eq!(
+            name(vertex.as_of(timestamp(2), timestamp(15))),
+            Some("Alice")
+        );
+        assert_eq!(name(vertex.as_of(timestamp(2), timestamp(25))), None);
+    }
+
+    #[test]
+    fn as_of_pins_the_other_axis_to_now() {
+        let now = Timestamp::now().time;
+        let later = Timestamp::from(now + time::Duration::HOUR);
+
+        // valid now, until an hour from now
+        let vertex = EntityVertex(BTreeMap::from([edition(
+            LeftClosedTemporalInterval::between(timestamp(0), later),
+            LeftClosedTemporalInterval::since(timestamp(10)),
+            "Alice",
+        )]));
+        assert_eq!(
+            name(vertex.as_of_transaction_time(timestamp(15))),
+            Some("Alice")
+        );
+
+        // only valid from an hour from now
+        let vertex = EntityVertex(BTreeMap::from([edition(
+            LeftClosedTemporalInterval::since(later),
+            LeftClosedTemporalInterval::since(timestamp(10)),
+            "Alice",
+        )]));
+        assert_eq!(name(vertex.as_of_transaction_time(timestamp(15))), None);
+        assert_eq!(name(vertex.as_of_decision_time(later)), Some("Alice"));
+
+        // recorded now, but superseded an hour from now
+        let vertex = EntityVertex(BTreeMap::from([edition(
+            LeftClosedTemporalInterval::since(timestamp(0)),
+            LeftClosedTemporalInterval::between(timestamp(10), later),
+            "Alice",
+        )]));
+        assert_eq!(
+            name(vertex.as_of_decision_time(timestamp(5))),
+            Some("Alice")
+        );
+    }
+
+    #[test]
+    fn editions_in() {
+        let vertex = vertex();
+
+        let editions = vertex.editions_in_decision_time(LeftClosedTemporalInterval::between(
+            timestamp(16),
+            timestamp(17),
+        ));
+        assert_eq!(editions.count(), 2);
+
+        let editions = vertex.editions_in_transaction_time(LeftClosedTemporalInterval::between(
+            timestamp(10),
+            timestamp(21),
+        ));
+        assert_eq!(editions.count(), 2);
+
+        let editions = vertex.editions_in(
+            LeftClosedTemporalInterval::between(timestamp(0), timestamp(10)),
+            LeftClosedTemporalInterval::since(timestamp(20)),
+        );
+        assert_eq!(editions.count(), 1);
+    }
 }
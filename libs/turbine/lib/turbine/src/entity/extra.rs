@@ -0,0 +1,404 @@
+//! Properties of an entity, which are not part of its entity type.
+//!
+//! Depending on the configuration, generated entity types either drop these properties, keep them
+//! in an `extra` field, which is serialized alongside the known properties, or reject them.
+
+use alloc::{
+    borrow::{Cow, ToOwned},
+    format,
+    string::String,
+};
+
+use error_stack::{Report, Result};
+use hashbrown::HashMap;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{raw, raw::RawProperties, GenericEntityError};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct ExtraProperties(pub HashMap<String, Value>);
+
+impl ExtraProperties {
+    #[must_use]
+    pub fn as_ref(&self) -> ExtraPropertiesRef<'_> {
+        ExtraPropertiesRef(
+            self.0
+                .iter()
+                .map(|(key, value)| (Cow::Borrowed(key.as_str()), Cow::Borrowed(value)))
+                .collect(),
+        )
+    }
+
+    #[must_use]
+    pub fn as_mut(&mut self) -> ExtraPropertiesMut<'_> {
+        ExtraPropertiesMut(
+            self.0
+                .iter_mut()
+                .map(|(key, value)| (key.as_str(), value))
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct ExtraPropertiesRef<'a>(pub HashMap<Cow<'a, str>, Cow<'a, Value>>);
+
+impl ExtraPropertiesRef<'_> {
+    #[must_use]
+    pub fn into_owned(self) -> ExtraProperties {
+        ExtraProperties(
+            self.0
+                .into_iter()
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct ExtraPropertiesMut<'a>(pub HashMap<&'a str, &'a mut Value>);
+
+impl ExtraPropertiesMut<'_> {
+    #[must_use]
+    pub fn into_owned(self) -> ExtraProperties {
+        ExtraProperties(
+            self.0
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value.clone()))
+                .collect(),
+        )
+    }
+}
+
+fn is_unknown(keys: &[&str], key: &str) -> bool {
+    !keys.contains(&key)
+}
+
+/// Ensure that every property is one of `keys`.
+///
+/// # Errors
+///
+/// if there are unknown properties, every unknown property is attached to the report
+pub fn ensure_known<K: AsRef<str>>(
+    keys: &[&str],
+    properties: impl IntoIterator<Item = K>,
+) -> Result<(), GenericEntityError> {
+    let mut report: Option<Report<GenericEntityError>> = None;
+
+    for key in properties {
+        let key = key.as_ref();
+
+        if is_unknown(keys, key) {
+            let error = Report::new(GenericEntityError::UnknownProperty)
+                .attach_printable(format!("{key} is not part of the entity type"));
+
+            match &mut report {
+                Some(report) => report.extend_one(error),
+                None => report = Some(error),
+            }
+        }
+    }
+
+    report.map_or(Ok(()), Err)
+}
+
+/// Remove every property, which is not one of `keys`.
+#[must_use]
+pub fn split_off(keys: &[&str], properties: &mut HashMap<String, Value>) -> ExtraProperties {
+    ExtraProperties(
+        properties
+            .extract_if(|key, _| is_unknown(keys, key))
+            .collect(),
+    )
+}
+
+/// Borrow every property, which is not one of `keys`.
+#[must_use]
+pub fn collect_ref<'a>(
+    keys: &[&str],
+    properties: &'a HashMap<String, Value>,
+) -> ExtraPropertiesRef<'a> {
+    ExtraPropertiesRef(
+        properties
+            .iter()
+            .filter(|(key, _)| is_unknown(keys, key))
+            .map(|(key, value)| (Cow::Borrowed(key.as_str()), Cow::Borrowed(value)))
+            .collect(),
+    )
+}
+
+/// Parse every raw property, which is not one of `keys`.
+///
+/// # Errors
+///
+/// if one of the properties cannot be parsed, e.g. because it is nested too deeply
+pub fn collect_raw<'a>(
+    keys: &[&str],
+    properties: &RawProperties<'a>,
+) -> Result<ExtraPropertiesRef<'a>, GenericEntityError> {
+    properties
+        .properties()
+        .iter()
+        .filter(|(key, _)| is_unknown(keys, key))
+        .map(|(key, value)| {
+            raw::to_value(value)
+                .map(|value| (key.clone(), Cow::Owned(value)))
+                .ok_or_else(|| {
+                    Report::new(GenericEntityError::ExtraProperty)
+                        .attach_printable(format!("unable to parse {key}"))
+                })
+        })
+        .collect::<core::result::Result<_, _>>()
+        .map(ExtraPropertiesRef)
+}
+
+/// Split the properties into the value of every one of `keys`, in the same order, and every other
+/// property.
+///
+/// Every value is borrowed in a single pass over `properties`, so that the mutable borrows of known
+/// and extra properties never overlap.
+#[must_use]
+pub fn split_mut<'a, const N: usize>(
+    keys: &[&str; N],
+    properties: &'a mut HashMap<String, Value>,
+) -> ([Option<&'a mut Value>; N], ExtraPropertiesMut<'a>) {
+    let mut known = [(); N].map(|()| None);
+    let mut extra = HashMap::new();
+
+    for (key, value) in properties.iter_mut() {
+        match keys.iter().position(|known| *known == key) {
+            Some(position) => known[position] = Some(value),
+            None => {
+                extra.insert(key.as_str(), value);
+            }
+        }
+    }
+
+    (known, ExtraPropertiesMut(extra))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{entity::Entity, fixture};
+
+    const KEYS: &[&str] = &["https://example.com/property-type/name/"];
+
+    fn properties() -> HashMap<String, Value> {
+        [
+            ("https://example.com/property-type/name/", json!("Alice")),
+            ("https://example.com/property-type/age/", json!(42)),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_owned(), value))
+        .collect()
+    }
+
+    #[test]
+    fn collect() {
+        let mut properties = properties();
+
+        let extra = collect_ref(KEYS, &properties);
+        assert_eq!(
+            serde_json::to_value(&extra).expect("should be serializable"),
+            json!({ "https://example.com/property-type/age/": 42 })
+        );
+        let owned = extra.into_owned();
+
+        let ([name], mut extra) = split_mut(&[KEYS[0]], &mut properties);
+        let name = name.expect("should be present");
+        **extra
+            .0
+            .get_mut("https://example.com/property-type/age/")
+            .expect("should be present") = json!(43);
+        *name = json!("Bob");
+        assert_ne!(extra.into_owned(), owned);
+
+        assert_eq!(
+            properties["https://example.com/property-type/name/"],
+            json!("Bob")
+        );
+        assert_eq!(
+            properties["https://example.com/property-type/age/"],
+            json!(43)
+        );
+
+        let extra = split_off(KEYS, &mut properties);
+        assert_eq!(
+            serde_json::to_value(&extra).expect("should be serializable"),
+            json!({ "https://example.com/property-type/age/": 43 })
+        );
+        assert_eq!(properties.len(), 1);
+    }
+
+    const NAME: &str = "https://example.com/property-type/name/";
+    const AGE: &str = "https://example.com/property-type/age/";
+
+    // the properties of an entity type with a single optional property, in the shape codegen
+    // generates them with `UnknownProperties::Preserve`
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    struct Properties {
+        #[serde(rename = "https://example.com/property-type/name/")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<Value>,
+        #[serde(flatten)]
+        extra: ExtraProperties,
+    }
+
+    impl Properties {
+        fn try_from_value(mut properties: HashMap<String, Value>) -> Self {
+            let extra = split_off(KEYS, &mut properties);
+            let name = properties.remove(NAME);
+
+            Self { name, extra }
+        }
+
+        fn as_ref(&self) -> PropertiesRef<'_> {
+            PropertiesRef {
+                name: self.name.as_ref(),
+                extra: self.extra.as_ref(),
+            }
+        }
+
+        fn as_mut(&mut self) -> PropertiesMut<'_> {
+            PropertiesMut {
+                name: self.name.as_mut(),
+                extra: self.extra.as_mut(),
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize)]
+    struct PropertiesRef<'a> {
+        #[serde(rename = "https://example.com/property-type/name/")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<&'a Value>,
+        #[serde(flatten)]
+        extra: ExtraPropertiesRef<'a>,
+    }
+
+    impl<'a> PropertiesRef<'a> {
+        fn try_from_value(properties: &'a HashMap<String, Value>) -> Self {
+            let extra = collect_ref(KEYS, properties);
+            let name = properties.get(NAME);
+
+            Self { name, extra }
+        }
+
+        fn into_owned(self) -> Properties {
+            Properties {
+                name: self.name.cloned(),
+                extra: self.extra.into_owned(),
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize)]
+    struct PropertiesMut<'a> {
+        #[serde(rename = "https://example.com/property-type/name/")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<&'a mut Value>,
+        #[serde(flatten)]
+        extra: ExtraPropertiesMut<'a>,
+    }
+
+    impl<'a> PropertiesMut<'a> {
+        fn try_from_value(properties: &'a mut HashMap<String, Value>) -> Self {
+            let ([name], extra) = split_mut(&[NAME], properties);
+
+            Self { name, extra }
+        }
+
+        fn into_owned(self) -> Properties {
+            Properties {
+                name: self.name.map(|name| name.clone()),
+                extra: self.extra.into_owned(),
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut entity: Entity = fixture::entity()
+            .properties(json!({ NAME: "Alice", AGE: 42 }))
+            .build();
+        let expected = json!({ NAME: "Alice", AGE: 42 });
+
+        let mut owned = Properties::try_from_value(entity.properties.0.clone());
+        assert_eq!(owned.extra.0[AGE], json!(42));
+        assert_eq!(
+            serde_json::to_value(&owned).expect("should be serializable"),
+            expected
+        );
+
+        let borrowed = PropertiesRef::try_from_value(&entity.properties.0);
+        assert_eq!(
+            serde_json::to_value(&borrowed).expect("should be serializable"),
+            expected
+        );
+        assert_eq!(borrowed.into_owned(), owned);
+        assert_eq!(owned.as_ref().into_owned(), owned);
+
+        let mut borrowed = PropertiesMut::try_from_value(&mut entity.properties.0);
+        assert_eq!(
+            serde_json::to_value(&borrowed).expect("should be serializable"),
+            expected
+        );
+        **borrowed.extra.0.get_mut(AGE).expect("should be present") = json!(43);
+        let changed = borrowed.into_owned();
+        assert_eq!(changed.extra.0[AGE], json!(43));
+        assert_eq!(entity.properties.0[AGE], json!(43));
+
+        **owned
+            .as_mut()
+            .extra
+            .0
+            .get_mut(AGE)
+            .expect("should be present") = json!(43);
+        assert_eq!(owned, changed);
+        assert_eq!(
+            serde_json::to_value(&owned).expect("should be serializable"),
+            json!({ NAME: "Alice", AGE: 43 })
+        );
+    }
+
+    #[test]
+    fn reject() {
+        let entity: Entity = fixture::entity()
+            .properties(json!({
+                NAME: "Alice",
+                "https://example.com/property-type/unknown/": true
+            }))
+            .build();
+
+        // `UnknownProperties::Reject` checks the keys before converting any property
+        let error = ensure_known(KEYS, entity.properties.0.keys()).expect_err("should be rejected");
+        assert!(matches!(
+            error.current_context(),
+            GenericEntityError::UnknownProperty
+        ));
+        assert!(format!("{error:?}")
+            .contains("https://example.com/property-type/unknown/ is not part of the entity type"));
+    }
+
+    #[test]
+    fn strict() {
+        let properties = properties();
+
+        ensure_known(KEYS, properties.keys()).expect_err("should reject unknown properties");
+        ensure_known(
+            &[
+                "https://example.com/property-type/name/",
+                "https://example.com/property-type/age/",
+            ],
+            properties.keys(),
+        )
+        .expect("should accept known properties");
+    }
+}
@@ -1,5 +1,8 @@
 //! Adapted copy of: <https://github.com/hashintel/hash/blob/715b69a7ae583036f989f5073acd6dc7022a1625/apps/hash-graph/lib/graph/src/shared/identifier/time.rs>
 
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
 use serde::{Deserialize, Serialize};
 use time::{serde::iso8601, OffsetDateTime};
 
@@ -10,12 +13,70 @@ pub enum OpenTemporalBound {
     Unbounded,
 }
 
+impl OpenTemporalBound {
+    #[must_use]
+    pub const fn timestamp(&self) -> Option<Timestamp> {
+        match self {
+            Self::Exclusive(timestamp) => Some(*timestamp),
+            Self::Unbounded => None,
+        }
+    }
+
+    /// Returns `true` if the bound, used as an upper bound, lies after `timestamp`.
+    fn is_after(&self, timestamp: Timestamp) -> bool {
+        match self {
+            Self::Exclusive(end) => *end > timestamp,
+            Self::Unbounded => true,
+        }
+    }
+}
+
+/// Upper bounds are ordered by their timestamp, an unbounded upper bound is greater than any
+/// exclusive bound.
+impl PartialOrd for OpenTemporalBound {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenTemporalBound {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Exclusive(lhs), Self::Exclusive(rhs)) => lhs.cmp(rhs),
+            (Self::Exclusive(_), Self::Unbounded) => Ordering::Less,
+            (Self::Unbounded, Self::Exclusive(_)) => Ordering::Greater,
+            (Self::Unbounded, Self::Unbounded) => Ordering::Equal,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", tag = "kind", content = "limit")]
 pub enum ClosedTemporalBound {
     Inclusive(Timestamp),
 }
 
+impl ClosedTemporalBound {
+    #[must_use]
+    pub const fn timestamp(&self) -> Timestamp {
+        match self {
+            Self::Inclusive(timestamp) => *timestamp,
+        }
+    }
+}
+
+impl PartialOrd for ClosedTemporalBound {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ClosedTemporalBound {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.timestamp().cmp(&other.timestamp())
+    }
+}
+
 /// Opaque structure to represent a single point in time.
 ///
 /// The type parameter `A` is the time axis to distinguish between different time axes at compile
@@ -30,15 +91,244 @@ pub struct Timestamp {
     pub time: OffsetDateTime,
 }
 
-#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+impl Timestamp {
+    #[must_use]
+    pub fn now() -> Self {
+        Self {
+            time: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+impl From<OffsetDateTime> for Timestamp {
+    fn from(time: OffsetDateTime) -> Self {
+        Self { time }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Interval<S, E> {
     pub start: S,
     pub end: E,
 }
 
-// TODO: we auto derive here, which is not correct. Problem: it is a lot more complicated to adapt
-//  the code as we removed some generics and complexity
-
 /// A temporal interval, where the lower bound is inclusive and the upper bound is either exclusive
 /// or unbounded.
 pub type LeftClosedTemporalInterval = Interval<ClosedTemporalBound, OpenTemporalBound>;
+
+impl LeftClosedTemporalInterval {
+    #[must_use]
+    pub const fn new(start: ClosedTemporalBound, end: OpenTemporalBound) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates an interval, which starts at `start` and is unbounded.
+    #[must_use]
+    pub const fn since(start: Timestamp) -> Self {
+        Self::new(
+            ClosedTemporalBound::Inclusive(start),
+            OpenTemporalBound::Unbounded,
+        )
+    }
+
+    /// Creates the interval `[start, end)`.
+    #[must_use]
+    pub const fn between(start: Timestamp, end: Timestamp) -> Self {
+        Self::new(
+            ClosedTemporalBound::Inclusive(start),
+            OpenTemporalBound::Exclusive(end),
+        )
+    }
+
+    /// Returns `true` if the interval does not contain any point in time.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        !self.end.is_after(self.start.timestamp())
+    }
+
+    /// Returns `true` if `timestamp` lies within the interval.
+    #[must_use]
+    pub fn contains_timestamp(&self, timestamp: Timestamp) -> bool {
+        self.start.timestamp() <= timestamp && self.end.is_after(timestamp)
+    }
+
+    /// Returns `true` if `other` lies completely within the interval.
+    #[must_use]
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && self.end >= other.end)
+    }
+
+    /// Returns `true` if both intervals have at least a single point in time in common.
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.end.is_after(other.start.timestamp())
+            && other.end.is_after(self.start.timestamp())
+            && !self.is_empty()
+            && !other.is_empty()
+    }
+
+    /// Returns `true` if one interval ends exactly where the other one starts.
+    #[must_use]
+    pub fn is_adjacent_to(&self, other: &Self) -> bool {
+        self.end == OpenTemporalBound::Exclusive(other.start.timestamp())
+            || other.end == OpenTemporalBound::Exclusive(self.start.timestamp())
+    }
+
+    /// Returns the interval, which is contained in both intervals, or `None` if they do not
+    /// overlap.
+    #[must_use]
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other)
+            .then(|| Self::new(self.start.max(other.start), self.end.min(other.end)))
+    }
+
+    /// Returns the interval, which covers both intervals, or `None` if there would be a gap
+    /// between them.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if other.is_empty() {
+            return Some(*self);
+        }
+
+        if self.is_empty() {
+            return Some(*other);
+        }
+
+        (self.overlaps(other) || self.is_adjacent_to(other))
+            .then(|| Self::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// Merges all overlapping or adjacent intervals, the returned intervals are sorted and
+    /// disjoint.
+    #[must_use]
+    pub fn merge(intervals: impl IntoIterator<Item = Self>) -> Vec<Self> {
+        let mut intervals: Vec<_> = intervals
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+            .collect();
+        intervals.sort();
+
+        let mut merged: Vec<Self> = Vec::with_capacity(intervals.len());
+
+        for interval in intervals {
+            if let Some(last) = merged.last_mut() {
+                if let Some(union) = last.union(&interval) {
+                    *last = union;
+                    continue;
+                }
+            }
+
+            merged.push(interval);
+        }
+
+        merged
+    }
+}
+
+/// Intervals are ordered by their lower bound first and their upper bound second.
+impl PartialOrd for LeftClosedTemporalInterval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LeftClosedTemporalInterval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start
+            .cmp(&other.start)
+            .then_with(|| self.end.cmp(&other.end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    fn timestamp(seconds: i64) -> Timestamp {
+        Timestamp::from(
+            OffsetDateTime::from_unix_timestamp(seconds).expect("should be a valid timestamp"),
+        )
+    }
+
+    fn interval(start: i64, end: Option<i64>) -> LeftClosedTemporalInterval {
+        end.map_or_else(
+            || LeftClosedTemporalInterval::since(timestamp(start)),
+            |end| LeftClosedTemporalInterval::between(timestamp(start), timestamp(end)),
+        )
+    }
+
+    #[test]
+    fn contains_timestamp() {
+        let value = interval(10, Some(20));
+
+        assert!(!value.contains_timestamp(timestamp(9)));
+        assert!(value.contains_timestamp(timestamp(10)));
+        assert!(value.contains_timestamp(timestamp(19)));
+        assert!(!value.contains_timestamp(timestamp(20)));
+
+        assert!(interval(10, None).contains_timestamp(timestamp(i64::from(u32::MAX))));
+    }
+
+    #[test]
+    fn overlaps() {
+        assert!(interval(10, Some(20)).overlaps(&interval(15, Some(25))));
+        assert!(interval(10, None).overlaps(&interval(15, Some(25))));
+        assert!(!interval(10, Some(20)).overlaps(&interval(20, Some(25))));
+        assert!(!interval(10, Some(10)).overlaps(&interval(0, None)));
+    }
+
+    #[test]
+    fn intersect() {
+        assert_eq!(
+            interval(10, Some(20)).intersect(&interval(15, None)),
+            Some(interval(15, Some(20)))
+        );
+        assert_eq!(interval(10, Some(20)).intersect(&interval(20, None)), None);
+    }
+
+    #[test]
+    fn union() {
+        assert_eq!(
+            interval(10, Some(20)).union(&interval(20, Some(30))),
+            Some(interval(10, Some(30)))
+        );
+        assert_eq!(
+            interval(10, Some(20)).union(&interval(15, None)),
+            Some(interval(10, None))
+        );
+        assert_eq!(interval(10, Some(20)).union(&interval(21, None)), None);
+    }
+
+    #[test]
+    fn merge() {
+        let merged = LeftClosedTemporalInterval::merge([
+            interval(30, Some(40)),
+            interval(0, Some(10)),
+            interval(5, Some(15)),
+            interval(15, Some(20)),
+            interval(25, Some(25)),
+        ]);
+
+        assert_eq!(merged, vec![interval(0, Some(20)), interval(30, Some(40))]);
+    }
+
+    #[test]
+    fn ordering() {
+        let mut intervals = vec![
+            interval(10, None),
+            interval(10, Some(20)),
+            interval(5, None),
+            interval(10, Some(15)),
+        ];
+        intervals.sort();
+
+        assert_eq!(intervals, vec![
+            interval(5, None),
+            interval(10, Some(15)),
+            interval(10, Some(20)),
+            interval(10, None),
+        ]);
+    }
+}
@@ -0,0 +1,214 @@
+//! Versioned wire models of the Graph API.
+//!
+//! Every version maps into the stable [`Entity`], which does not change with the Graph API.
+//! Deserializing an [`Entity`] directly reads it in the latest version, which accepts every earlier
+//! version as well, use [`from_value`] to select or detect the version explicitly instead.
+//! Entities are always serialized in the latest version.
+
+pub mod v1;
+pub mod v2;
+
+use alloc::string::String;
+
+use error_stack::{Report, Result};
+use serde_json::{Map, Value};
+
+use crate::{entity::Entity, WireError};
+
+const V1_METADATA: &[&str] = &[
+    "recordId",
+    "temporalVersioning",
+    "entityTypeId",
+    "provenance",
+    "archived",
+    "draft",
+];
+const V1_PROVENANCE: &[&str] = &["recordCreatedById", "recordArchivedById"];
+
+/// Version of the wire model.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum WireVersion {
+    /// A single entity type per entity, see [`v1`].
+    V1,
+    /// Multiple entity types per entity, confidence and per-property metadata, see [`v2`].
+    V2,
+}
+
+impl WireVersion {
+    pub const LATEST: Self = Self::V2;
+
+    /// Detect the version of the wire representation of an entity.
+    ///
+    /// An entity is only considered to be [`Self::V1`] if it uses none of the fields introduced
+    /// later on, everything else is read as the latest version, which tolerates unknown fields.
+    #[must_use]
+    pub fn detect(entity: &Value) -> Self {
+        let Some(metadata) = entity.get("metadata").and_then(Value::as_object) else {
+            return Self::LATEST;
+        };
+
+        let is_v1 = |object: &Map<String, Value>, fields: &[&str]| {
+            object.keys().all(|key| fields.contains(&key.as_str()))
+        };
+
+        let provenance = match metadata.get("provenance") {
+            Some(Value::Object(provenance)) => is_v1(provenance, V1_PROVENANCE),
+            _ => true,
+        };
+
+        if provenance && is_v1(metadata, V1_METADATA) {
+            Self::V1
+        } else {
+            Self::LATEST
+        }
+    }
+}
+
+/// Deserialize an entity in the given version of the wire model, or detect the version if `None`.
+///
+/// # Errors
+///
+/// if the value is not a valid entity in the (detected) version
+pub fn from_value(value: Value, version: Option<WireVersion>) -> Result<Entity, WireError> {
+    let version = version.unwrap_or_else(|| WireVersion::detect(&value));
+
+    let entity = match version {
+        WireVersion::V1 => serde_json::from_value::<v1::Entity>(value).map(Entity::from),
+        WireVersion::V2 => serde_json::from_value::<v2::Entity>(value).map(Entity::from),
+    };
+
+    entity.map_err(|error| Report::new(WireError::Deserialize).attach_printable(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString};
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::fixture;
+
+    #[test]
+    fn v1() {
+        let value = fixture::entity().into_value();
+        assert_eq!(WireVersion::detect(&value), WireVersion::V1);
+
+        let entity = from_value(value.clone(), None).expect("should be a valid entity");
+        assert_eq!(entity.metadata.entity_type_ids.iter().count(), 1);
+        assert_eq!(
+            serde_json::from_value::<Entity>(value.clone()).expect("should be a valid entity"),
+            entity
+        );
+
+        let mut value = value;
+        value["metadata"]["confidence"] = json!(0.5);
+        from_value(value, Some(WireVersion::V1)).expect_err("v1 should reject unknown fields");
+    }
+
+    #[test]
+    fn v2() {
+        let mut value = fixture::entity()
+            .entity_id(&format!("{}~{}", fixture::ENTITY_ID, fixture::EDITION_ID))
+            .entity_types(&[
+                "https://example.com/entity-type/person/v/1",
+                "https://example.com/entity-type/employee/v/1",
+            ])
+            .metadata("confidence", json!(0.5))
+            .metadata(
+                "properties",
+                json!({
+                    "https://example.com/property-type/name/": { "confidence": 0.25 }
+                }),
+            )
+            .metadata("temporalTags", json!(["imported"]))
+            .link_data(json!({
+                "leftEntityId": fixture::ENTITY_ID,
+                "rightEntityId": fixture::ENTITY_ID,
+                "leftToRightOrder": 1,
+                "confidence": 0.5
+            }))
+            .into_value();
+        value["metadata"]["provenance"]["origin"] = json!({ "type": "api" });
+        value["metadata"]["temporalVersioning"]["pinnedAxis"] = json!("transactionTime");
+        value["permissions"] = json!({ "edit": true });
+        assert_eq!(WireVersion::detect(&value), WireVersion::V2);
+
+        let entity = from_value(value.clone(), None).expect("should be a valid entity");
+        let metadata = &entity.metadata;
+
+        assert_eq!(metadata.entity_type_ids.iter().count(), 2);
+        assert_eq!(metadata.confidence, serde_json::Number::from_f64(0.5));
+        assert_eq!(
+            metadata.property_metadata,
+            Some(json!({
+                "https://example.com/property-type/name/": { "confidence": 0.25 }
+            }))
+        );
+        assert_eq!(
+            metadata.additional.get("temporalTags"),
+            Some(&json!(["imported"]))
+        );
+        assert_eq!(
+            metadata.provenance.additional.get("origin"),
+            Some(&json!({ "type": "api" }))
+        );
+        assert!(metadata.record_id.entity_id.draft_id.is_some());
+        assert_eq!(
+            metadata.temporal_versioning.additional.get("pinnedAxis"),
+            Some(&json!("transactionTime"))
+        );
+
+        let link_data = entity.link_data.as_ref().expect("should be a link");
+        assert_eq!(link_data.order.left_to_right, Some(1));
+        assert_eq!(link_data.additional.get("confidence"), Some(&json!(0.5)));
+        assert_eq!(
+            entity.additional.get("permissions"),
+            Some(&json!({ "edit": true }))
+        );
+
+        let serialized = serde_json::to_value(&entity).expect("should be serializable");
+        for field in ["entityTypeIds", "confidence", "properties", "temporalTags"] {
+            assert_eq!(serialized["metadata"][field], value["metadata"][field]);
+        }
+        assert_eq!(
+            serialized["metadata"]["provenance"]["origin"],
+            value["metadata"]["provenance"]["origin"]
+        );
+        assert_eq!(
+            serialized["metadata"]["recordId"]["entityId"],
+            value["metadata"]["recordId"]["entityId"]
+        );
+        assert_eq!(
+            serialized["metadata"]["temporalVersioning"]["pinnedAxis"],
+            value["metadata"]["temporalVersioning"]["pinnedAxis"]
+        );
+        assert_eq!(
+            serialized["linkData"]["confidence"],
+            value["linkData"]["confidence"]
+        );
+        assert_eq!(serialized["permissions"], value["permissions"]);
+        assert_eq!(
+            serde_json::from_value::<Entity>(serialized).expect("should be a valid entity"),
+            entity
+        );
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        let value = fixture::entity()
+            .metadata("archived", json!("no"))
+            .into_value();
+
+        let error = serde_json::from_value::<Entity>(value)
+            .expect_err("should reject a non-boolean `archived`")
+            .to_string();
+        assert!(error.contains("invalid type"), "{error}");
+
+        let value = fixture::entity().entity_types(&[]).into_value();
+        let error = serde_json::from_value::<Entity>(value)
+            .expect_err("should reject an entity without entity types")
+            .to_string();
+        assert!(error.contains("invalid length 0"), "{error}");
+    }
+}
@@ -0,0 +1,75 @@
+//! Wire format of the Graph API before entities could have multiple entity types.
+//!
+//! The format is closed, unknown fields are rejected.
+
+use alloc::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use type_system::url::VersionedUrl;
+use uuid::Uuid;
+
+use crate::entity::{self, EntityProperties, EntityRecordId, EntityTemporalMetadata, LinkData};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct ProvenanceMetadata {
+    pub record_created_by_id: Uuid,
+    pub record_archived_by_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct EntityMetadata {
+    pub record_id: EntityRecordId,
+    pub temporal_versioning: EntityTemporalMetadata,
+    pub entity_type_id: VersionedUrl,
+    pub provenance: ProvenanceMetadata,
+    pub archived: bool,
+    pub draft: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Entity {
+    pub properties: EntityProperties,
+    #[serde(default)]
+    pub link_data: Option<LinkData>,
+    pub metadata: EntityMetadata,
+}
+
+impl From<ProvenanceMetadata> for entity::ProvenanceMetadata {
+    fn from(value: ProvenanceMetadata) -> Self {
+        Self {
+            record_created_by_id: value.record_created_by_id,
+            record_archived_by_id: value.record_archived_by_id,
+            additional: BTreeMap::new(),
+        }
+    }
+}
+
+impl From<EntityMetadata> for entity::EntityMetadata {
+    fn from(value: EntityMetadata) -> Self {
+        Self {
+            record_id: value.record_id,
+            temporal_versioning: value.temporal_versioning,
+            entity_type_ids: value.entity_type_id.into(),
+            provenance: value.provenance.into(),
+            archived: value.archived,
+            draft: value.draft,
+            confidence: None,
+            property_metadata: None,
+            additional: BTreeMap::new(),
+        }
+    }
+}
+
+impl From<Entity> for entity::Entity {
+    fn from(value: Entity) -> Self {
+        Self {
+            properties: value.properties,
+            link_data: value.link_data,
+            metadata: value.metadata.into(),
+            additional: BTreeMap::new(),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Wire format of the Graph API, which supports multiple entity types per entity and attaches
+//! confidence and per-property metadata.
+//!
+//! The format is open, unknown metadata is kept in `additional`, so that it survives a round trip.
+
+use alloc::{collections::BTreeMap, string::String};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+use uuid::Uuid;
+
+use crate::entity::{
+    self, EntityProperties, EntityRecordId, EntityTemporalMetadata, EntityTypeIds, LinkData,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceMetadata {
+    pub record_created_by_id: Uuid,
+    #[serde(default)]
+    pub record_archived_by_id: Option<Uuid>,
+    #[serde(flatten)]
+    pub additional: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityMetadata {
+    pub record_id: EntityRecordId,
+    pub temporal_versioning: EntityTemporalMetadata,
+    #[serde(alias = "entityTypeId")]
+    pub entity_type_ids: EntityTypeIds,
+    pub provenance: ProvenanceMetadata,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Number>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Value>,
+    #[serde(flatten)]
+    pub additional: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entity {
+    pub properties: EntityProperties,
+    #[serde(default)]
+    pub link_data: Option<LinkData>,
+    pub metadata: EntityMetadata,
+    #[serde(flatten)]
+    pub additional: BTreeMap<String, Value>,
+}
+
+impl From<ProvenanceMetadata> for entity::ProvenanceMetadata {
+    fn from(value: ProvenanceMetadata) -> Self {
+        Self {
+            record_created_by_id: value.record_created_by_id,
+            record_archived_by_id: value.record_archived_by_id,
+            additional: value.additional,
+        }
+    }
+}
+
+impl From<entity::ProvenanceMetadata> for ProvenanceMetadata {
+    fn from(value: entity::ProvenanceMetadata) -> Self {
+        Self {
+            record_created_by_id: value.record_created_by_id,
+            record_archived_by_id: value.record_archived_by_id,
+            additional: value.additional,
+        }
+    }
+}
+
+impl From<EntityMetadata> for entity::EntityMetadata {
+    fn from(value: EntityMetadata) -> Self {
+        Self {
+            record_id: value.record_id,
+            temporal_versioning: value.temporal_versioning,
+            entity_type_ids: value.entity_type_ids,
+            provenance: value.provenance.into(),
+            archived: value.archived,
+            draft: value.draft,
+            confidence: value.confidence,
+            property_metadata: value.properties,
+            additional: value.additional,
+        }
+    }
+}
+
+impl From<entity::EntityMetadata> for EntityMetadata {
+    fn from(value: entity::EntityMetadata) -> Self {
+        Self {
+            record_id: value.record_id,
+            temporal_versioning: value.temporal_versioning,
+            entity_type_ids: value.entity_type_ids,
+            provenance: value.provenance.into(),
+            archived: value.archived,
+            draft: value.draft,
+            confidence: value.confidence,
+            properties: value.property_metadata,
+            additional: value.additional,
+        }
+    }
+}
+
+impl From<Entity> for entity::Entity {
+    fn from(value: Entity) -> Self {
+        Self {
+            properties: value.properties,
+            link_data: value.link_data,
+            metadata: value.metadata.into(),
+            additional: value.additional,
+        }
+    }
+}
+
+impl From<entity::Entity> for Entity {
+    fn from(value: entity::Entity) -> Self {
+        Self {
+            properties: value.properties,
+            link_data: value.link_data,
+            metadata: value.metadata.into(),
+            additional: value.additional,
+        }
+    }
+}
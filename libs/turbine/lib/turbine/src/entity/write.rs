@@ -0,0 +1,148 @@
+//! Writes into the properties of the generated `Mut` types.
+//!
+//! A `Mut` type holds its [`Backing`], instead of mutable references into the values of the
+//! properties, as adding or removing a property would invalidate them. The typed properties are
+//! only derived on demand, for as long as they are borrowed.
+
+use alloc::{string::String, vec, vec::Vec};
+
+use error_stack::{Report, Result};
+use hashbrown::HashMap;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+use crate::GenericEntityError;
+
+/// Storage of the properties, which a generated `Mut` type reads from and writes into.
+#[derive(Debug)]
+pub enum Backing<'a, T> {
+    /// Properties of an [`Entity`], in their wire representation.
+    ///
+    /// [`Entity`]: crate::entity::Entity
+    Entity(&'a mut HashMap<String, Value>),
+    /// Properties of an owned entity type.
+    Owned(&'a mut T),
+}
+
+impl<T: Serialize> Serialize for Backing<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Entity(properties) => properties.serialize(serializer),
+            Self::Owned(properties) => properties.serialize(serializer),
+        }
+    }
+}
+
+/// Serialize `value` into its wire representation and validate it.
+///
+/// # Errors
+///
+/// if the value cannot be serialized, or is not valid according to `is_valid`
+pub fn encode<T: Serialize + ?Sized>(
+    key: &'static str,
+    value: &T,
+    is_valid: impl FnOnce(&Value) -> bool,
+) -> Result<Value, GenericEntityError> {
+    let value = serde_json::to_value(value).map_err(|error| {
+        Report::new(GenericEntityError::Property)
+            .attach_printable(key)
+            .attach_printable(error)
+    })?;
+
+    if is_valid(&value) {
+        Ok(value)
+    } else {
+        Err(Report::new(GenericEntityError::Property)
+            .attach_printable(key)
+            .attach_printable("value is not valid for the property type"))
+    }
+}
+
+/// Append `value` to the array at `key`, the array is created if the property is absent.
+pub fn push(properties: &mut HashMap<String, Value>, key: &str, value: Value) {
+    match properties.get_mut(key) {
+        Some(Value::Array(values)) => values.push(value),
+        Some(other) => *other = Value::Array(vec![value]),
+        None => {
+            properties.insert(String::from(key), Value::Array(vec![value]));
+        }
+    }
+}
+
+/// Number of items in the array at `key`, `0` if the property is absent or not an array.
+#[must_use]
+pub fn len(properties: &HashMap<String, Value>, key: &str) -> usize {
+    properties
+        .get(key)
+        .and_then(Value::as_array)
+        .map_or(0, Vec::len)
+}
+
+/// Remove the item at `index` from the array at `key`.
+///
+/// Returns `false` if there is no item at `index`.
+pub fn remove(properties: &mut HashMap<String, Value>, key: &str, index: usize) -> bool {
+    match properties.get_mut(key) {
+        Some(Value::Array(values)) if index < values.len() => {
+            values.remove(index);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Equivalent of [`remove`] for the array of an owned entity type, `None` if it is absent.
+#[must_use]
+pub fn remove_item<T>(values: Option<&mut Vec<T>>, index: usize) -> bool {
+    match values {
+        Some(values) if index < values.len() => {
+            values.remove(index);
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const KEY: &str = "https://example.com/property-type/tags/";
+
+    #[test]
+    fn array() {
+        let mut properties = HashMap::new();
+
+        assert_eq!(len(&properties, KEY), 0);
+
+        push(&mut properties, KEY, json!("a"));
+        push(&mut properties, KEY, json!("b"));
+        assert_eq!(properties[KEY], json!(["a", "b"]));
+        assert_eq!(len(&properties, KEY), 2);
+
+        assert!(remove(&mut properties, KEY, 0));
+        assert!(!remove(&mut properties, KEY, 1));
+        assert_eq!(properties[KEY], json!(["b"]));
+
+        let mut values = vec!["a"];
+        assert!(!remove_item(Some(&mut values), 1));
+        assert!(remove_item(Some(&mut values), 0));
+        assert!(!remove_item::<&str>(None, 0));
+    }
+
+    #[test]
+    fn validate() {
+        encode(KEY, &["a"], Value::is_array).expect("should be valid");
+
+        let error = encode(KEY, &"a", Value::is_array).expect_err("should be invalid");
+        assert!(matches!(
+            error.current_context(),
+            GenericEntityError::Property
+        ));
+    }
+}
@@ -1,5 +1,14 @@
+use alloc::{borrow::Cow, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+
+use error_stack::Result;
 use onlyerror::Error;
+use serde_json::Value;
+
+use crate::{path::Segment, VersionedUrl, VersionedUrlRef};
 
+// The key of a property is not part of the error, as keys may only be known at runtime, instead it
+// is recorded in the path of the `ErrorLocation`.
 #[derive(Debug, Copy, Clone, Error)]
 pub enum GenericPropertyError {
     #[error("unable to convert value into data-type")]
@@ -8,10 +17,10 @@ pub enum GenericPropertyError {
     Array,
     #[error("expected array as value")]
     ExpectedArray,
-    #[error("expected property with key {0} in object")]
-    ExpectedProperty(&'static str),
-    #[error("unable to convert value of property {0}")]
-    Property(&'static str),
+    #[error("expected property in object")]
+    ExpectedProperty,
+    #[error("unable to convert value of property")]
+    Property,
     #[error("expected object as value")]
     ExpectedObject,
     #[error("invalid value")]
@@ -22,12 +31,202 @@ pub enum GenericPropertyError {
 pub enum GenericEntityError {
     #[error("unable to convert one or values in array")]
     Array,
-    #[error("expected array as value at {0}")]
-    ExpectedArray(&'static str),
-    #[error("expected property with key {0} in object")]
-    ExpectedProperty(&'static str),
-    #[error("unable to convert value of property {0}")]
-    Property(&'static str),
+    #[error("expected array as value")]
+    ExpectedArray,
+    #[error("expected property in object")]
+    ExpectedProperty,
+    #[error("unable to convert value of property")]
+    Property,
     #[error("expected `LinkData`")]
     ExpectedLinkData,
+    #[error("unable to convert between versions of the entity type")]
+    Version,
+    #[error("entity has properties, which are not part of the entity type")]
+    UnknownProperty,
+    #[error("unable to preserve property, which is not part of the entity type")]
+    ExtraProperty,
+}
+
+#[derive(Debug, Copy, Clone, Error)]
+pub enum GenericDataTypeError {
+    #[error("value is not of the underlying data-type")]
+    Base,
+    #[error("value does not satisfy the `{0}` constraint")]
+    Constraint(&'static str),
+}
+
+#[derive(Debug, Copy, Clone, Error)]
+pub enum WireError {
+    #[error("unable to serialize value into wire format")]
+    Serialize,
+    #[error("expected properties to serialize into an object")]
+    ExpectedObject,
+    #[error("unable to deserialize value from wire format")]
+    Deserialize,
+}
+
+/// Kind of a JSON value, used to report what has been found instead of the expected value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum JsonKind {
+    Null,
+    Bool,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl JsonKind {
+    #[must_use]
+    pub const fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(_) => Self::Bool,
+            Value::Number(_) => Self::Number,
+            Value::String(_) => Self::String,
+            Value::Array(_) => Self::Array,
+            Value::Object(_) => Self::Object,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool => "boolean",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+        }
+    }
+}
+
+impl Display for JsonKind {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+/// Location of a value, which failed to convert, attached to the [`Report`] of a conversion.
+///
+/// The path is relative to the value the conversion started at, e.g. for
+/// [`EntityType::try_from_entity`] it is relative to the properties of the entity. Use
+/// [`at_field`] and [`at_index`] to prepend segments while the error propagates upwards.
+///
+/// [`Report`]: error_stack::Report
+/// [`EntityType::try_from_entity`]: crate::EntityType::try_from_entity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorLocation {
+    pub path: Vec<Segment<'static>>,
+    pub expected: VersionedUrl,
+    /// `None` if the value is missing
+    pub actual: Option<JsonKind>,
+}
+
+impl ErrorLocation {
+    #[must_use]
+    pub fn new(expected: VersionedUrlRef<'_>, actual: Option<JsonKind>) -> Self {
+        Self {
+            path: Vec::new(),
+            expected: expected.into_owned(),
+            actual,
+        }
+    }
+}
+
+impl Display for ErrorLocation {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.write_str("at $")?;
+
+        for segment in &self.path {
+            match segment {
+                Segment::Field(field) => write!(fmt, "[\"{}\"]", field.escape_debug())?,
+                Segment::Index(index) => write!(fmt, "[{index}]")?,
+            }
+        }
+
+        write!(fmt, ": expected {}, found ", self.expected)?;
+
+        match self.actual {
+            Some(actual) => Display::fmt(&actual, fmt),
+            None => fmt.write_str("nothing"),
+        }
+    }
+}
+
+fn prepend<T, C>(result: Result<T, C>, segment: &Segment<'static>) -> Result<T, C> {
+    result.map_err(|mut report| {
+        for frame in report.frames_mut() {
+            if let Some(location) = frame.downcast_mut::<ErrorLocation>() {
+                location.path.insert(0, segment.clone());
+            }
+        }
+
+        report
+    })
+}
+
+/// Prepend the field `key` to every [`ErrorLocation`] in the report.
+///
+/// # Errors
+///
+/// if `result` is an error, the locations are adjusted, but the error is returned unchanged
+pub fn at_field<T, C>(result: Result<T, C>, key: impl Into<Cow<'static, str>>) -> Result<T, C> {
+    prepend(result, &Segment::Field(key.into()))
+}
+
+/// Prepend the array index `index` to every [`ErrorLocation`] in the report.
+///
+/// # Errors
+///
+/// if `result` is an error, the locations are adjusted, but the error is returned unchanged
+pub fn at_index<T, C>(result: Result<T, C>, index: usize) -> Result<T, C> {
+    prepend(result, &Segment::Index(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use error_stack::Report;
+    use serde_json::json;
+
+    use super::*;
+    use crate::url;
+
+    const TEXT: VersionedUrlRef<'static> =
+        url!("https://blockprotocol.org/@blockprotocol/types/data-type/text/" / v / 1);
+
+    #[test]
+    fn json_kind() {
+        assert_eq!(JsonKind::of(&json!(null)), JsonKind::Null);
+        assert_eq!(JsonKind::of(&json!(1)), JsonKind::Number);
+        assert_eq!(JsonKind::of(&json!([])), JsonKind::Array);
+        assert_eq!(JsonKind::of(&json!({})), JsonKind::Object);
+    }
+
+    #[test]
+    fn path_is_prepended() {
+        let result: Result<(), GenericPropertyError> = Err(Report::new(GenericPropertyError::Data)
+            .attach_printable(ErrorLocation::new(TEXT, Some(JsonKind::Number))));
+
+        let result = at_index(result, 2);
+        let result = at_field(result, "https://example.com/property-type/name/");
+
+        let report = result.expect_err("should be an error");
+        let location = report
+            .downcast_ref::<ErrorLocation>()
+            .expect("should have a location");
+
+        assert_eq!(location.path, vec![
+            Segment::Field(Cow::Borrowed("https://example.com/property-type/name/")),
+            Segment::Index(2)
+        ]);
+        assert_eq!(
+            location.to_string(),
+            "at $[\"https://example.com/property-type/name/\"][2]: expected \
+             https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1, found number"
+        );
+    }
 }
@@ -0,0 +1,118 @@
+//! Entity fixtures in the JSON representation of the graph, which are shared by the tests.
+//!
+//! The module only depends on `serde` and `serde_json`, so that test targets outside of this
+//! crate, like the benchmarks and the validator of codegen, can include it through `#[path]`.
+// not every target, which includes this module, uses every fixture
+#![allow(dead_code)]
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+pub(crate) const ENTITY_ID: &str =
+    "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e~6c8f2d1a-5b7e-4f3a-8c9d-1e2f3a4b5c6d";
+pub(crate) const EDITION_ID: &str = "0b1c2d3e-4f5a-4b6c-8d7e-9f0a1b2c3d4e";
+pub(crate) const ACTOR_ID: &str = "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e";
+pub(crate) const PERSON: &str = "https://example.com/entity-type/person/v/1";
+pub(crate) const TIMESTAMP: &str = "2023-01-01T00:00:00Z";
+
+/// An interval, which starts inclusively at `start` and is unbounded.
+pub(crate) fn since(start: &str) -> Value {
+    json!({
+        "start": { "kind": "inclusive", "limit": start },
+        "end": { "kind": "unbounded" }
+    })
+}
+
+/// A [`Person`] entity without any properties, which is valid since [`TIMESTAMP`] on both axes.
+///
+/// [`Person`]: PERSON
+pub(crate) fn entity() -> EntityFixture {
+    EntityFixture(json!({
+        "properties": {},
+        "metadata": {
+            "recordId": {
+                "entityId": ENTITY_ID,
+                "editionId": EDITION_ID
+            },
+            "temporalVersioning": {
+                "decisionTime": since(TIMESTAMP),
+                "transactionTime": since(TIMESTAMP)
+            },
+            "entityTypeId": PERSON,
+            "provenance": {
+                "recordCreatedById": ACTOR_ID
+            },
+            "archived": false,
+            "draft": false
+        }
+    }))
+}
+
+pub(crate) struct EntityFixture(Value);
+
+impl EntityFixture {
+    #[must_use]
+    pub(crate) fn entity_id(mut self, id: &str) -> Self {
+        self.0["metadata"]["recordId"]["entityId"] = json!(id);
+        self
+    }
+
+    /// Sets the single entity type of the first version of the wire format.
+    #[must_use]
+    pub(crate) fn entity_type(mut self, id: &str) -> Self {
+        self.0["metadata"]["entityTypeId"] = json!(id);
+        self
+    }
+
+    /// Replaces the single entity type with the list of the second version of the wire format.
+    #[must_use]
+    pub(crate) fn entity_types(mut self, ids: &[&str]) -> Self {
+        let metadata = self.0["metadata"]
+            .as_object_mut()
+            .expect("should be an object");
+        metadata.remove("entityTypeId");
+        metadata.insert("entityTypeIds".into(), json!(ids));
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn properties(mut self, properties: Value) -> Self {
+        self.0["properties"] = properties;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn link_data(mut self, link_data: Value) -> Self {
+        self.0["linkData"] = link_data;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn decision_time(mut self, interval: Value) -> Self {
+        self.0["metadata"]["temporalVersioning"]["decisionTime"] = interval;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn transaction_time(mut self, interval: Value) -> Self {
+        self.0["metadata"]["temporalVersioning"]["transactionTime"] = interval;
+        self
+    }
+
+    /// Sets a field of the metadata, which has no dedicated setter.
+    #[must_use]
+    pub(crate) fn metadata(mut self, key: &str, value: Value) -> Self {
+        self.0["metadata"][key] = value;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn into_value(self) -> Value {
+        self.0
+    }
+
+    /// Deserializes the fixture, usually into an `Entity`.
+    pub(crate) fn build<T: DeserializeOwned>(self) -> T {
+        serde_json::from_value(self.0).expect("should be a valid entity")
+    }
+}
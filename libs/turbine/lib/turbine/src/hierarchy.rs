@@ -1,69 +1,99 @@
-use core::iter::{empty, once};
-
 use crate::{TypeUrl, VersionedUrlRef};
 
-#[rustfmt::skip]
-macro_rules! all_the_tuples {
-    ($name:ident) => {
-        $name!(T1);
-        $name!(T1, T2);
-        $name!(T1, T2, T3);
-        $name!(T1, T2, T3, T4);
-        $name!(T1, T2, T3, T4, T5);
-        $name!(T1, T2, T3, T4, T5, T6);
-        $name!(T1, T2, T3, T4, T5, T6, T7);
-        $name!(T1, T2, T3, T4, T5, T6, T7, T8);
-        $name!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
-        $name!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
-        $name!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
-        $name!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
-        $name!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
-        $name!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14);
-        $name!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15);
-        $name!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16);
-    };
-}
+/// Marker trait, which is implemented for every type that inherits from `P`, either directly or
+/// indirectly.
+///
+/// This allows functions to require a subtype at compile time, e.g.
+/// `fn greet(person: &impl InheritsFrom<Person>)`.
+pub trait InheritsFrom<P: TypeUrl + ?Sized>: TypeUrl {}
 
-pub trait TypeHierarchyResolution {
-    type Iterator: Iterator<Item = VersionedUrlRef<'static>>;
+const fn str_eq(lhs: &str, rhs: &str) -> bool {
+    let lhs = lhs.as_bytes();
+    let rhs = rhs.as_bytes();
 
-    fn resolve() -> Self::Iterator;
-}
+    if lhs.len() != rhs.len() {
+        return false;
+    }
 
-impl<T> TypeHierarchyResolution for T
-where
-    T: TypeUrl,
-{
-    type Iterator = impl Iterator<Item = VersionedUrlRef<'static>>;
+    let mut index = 0;
+    while index < lhs.len() {
+        if lhs[index] != rhs[index] {
+            return false;
+        }
 
-    fn resolve() -> Self::Iterator {
-        once(T::ID)
+        index += 1;
     }
+
+    true
 }
 
-impl TypeHierarchyResolution for () {
-    type Iterator = impl Iterator<Item = VersionedUrlRef<'static>>;
+const fn url_eq(lhs: VersionedUrlRef, rhs: VersionedUrlRef) -> bool {
+    lhs.version() == rhs.version() && str_eq(lhs.base().as_str(), rhs.base().as_str())
+}
 
-    fn resolve() -> Self::Iterator {
-        empty()
+/// Returns `true` if `id` is `parent` or `parent` is one of the `ancestors`.
+///
+/// This is a `const fn`, so that it can be evaluated at compile time, see
+/// [`TypeUrl::is_subtype_of`].
+#[must_use]
+pub const fn is_subtype_of(
+    id: VersionedUrlRef,
+    ancestors: &[VersionedUrlRef],
+    parent: VersionedUrlRef,
+) -> bool {
+    if url_eq(id, parent) {
+        return true;
     }
-}
 
-macro_rules! impl_inherits_from {
-    ($($name:ident),*) => {
-        impl<$($name),*> TypeHierarchyResolution for ($($name,)*)
-        where
-            $($name: TypeHierarchyResolution,)*
-        {
-            type Iterator = impl Iterator<Item = VersionedUrlRef<'static>>;
-
-            fn resolve() -> Self::Iterator {
-                let iter = empty();
-                $(let iter = iter.chain($name::resolve());)*
-                iter
-            }
+    let mut index = 0;
+    while index < ancestors.len() {
+        if url_eq(ancestors[index], parent) {
+            return true;
         }
-    };
+
+        index += 1;
+    }
+
+    false
 }
 
-all_the_tuples!(impl_inherits_from);
+#[cfg(test)]
+mod tests {
+    use crate::{url, TypeUrl, VersionedUrlRef};
+
+    struct Thing;
+    struct Person;
+    struct Employee;
+
+    impl TypeUrl for Thing {
+        const ID: VersionedUrlRef<'static> = url!("https://example.com/entity-type/thing/" / v / 1);
+    }
+
+    impl TypeUrl for Person {
+        const ANCESTORS: &'static [VersionedUrlRef<'static>] = &[Thing::ID];
+        const ID: VersionedUrlRef<'static> =
+            url!("https://example.com/entity-type/person/" / v / 1);
+    }
+
+    impl TypeUrl for Employee {
+        const ANCESTORS: &'static [VersionedUrlRef<'static>] = &[Person::ID, Thing::ID];
+        const ID: VersionedUrlRef<'static> =
+            url!("https://example.com/entity-type/employee/" / v / 1);
+    }
+
+    #[test]
+    fn subtype() {
+        const _: () = assert!(super::is_subtype_of(
+            Employee::ID,
+            Employee::ANCESTORS,
+            Thing::ID
+        ));
+
+        assert!(Employee::is_subtype_of::<Employee>());
+        assert!(Employee::is_subtype_of::<Person>());
+        assert!(Person::is_subtype_of::<Thing>());
+
+        assert!(!Person::is_subtype_of::<Employee>());
+        assert!(!Thing::is_subtype_of::<Person>());
+    }
+}
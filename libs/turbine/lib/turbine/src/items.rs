@@ -0,0 +1,86 @@
+use error_stack::{Report, Result};
+use onlyerror::Error;
+
+/// Number of items of an array, which violates the `minItems` or `maxItems` of the schema.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Error)]
+pub enum ItemCountError {
+    #[error("expected at least {expected} items, found {actual}")]
+    TooFew { expected: usize, actual: usize },
+    #[error("expected at most {expected} items, found {actual}")]
+    TooMany { expected: usize, actual: usize },
+}
+
+/// Check the number of items of an array against `minItems` and `maxItems`.
+///
+/// # Errors
+///
+/// - [`ItemCountError::TooFew`], if there are less than `min` items
+/// - [`ItemCountError::TooMany`], if there are more than `max` items
+pub fn ensure_item_count(
+    actual: usize,
+    min: Option<usize>,
+    max: Option<usize>,
+) -> Result<(), ItemCountError> {
+    if let Some(expected) = min {
+        if actual < expected {
+            return Err(Report::new(ItemCountError::TooFew { expected, actual }));
+        }
+    }
+
+    if let Some(expected) = max {
+        if actual > expected {
+            return Err(Report::new(ItemCountError::TooMany { expected, actual }));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if `actual` is within `minItems` and `maxItems`, see [`ensure_item_count`].
+#[must_use]
+pub const fn is_valid_item_count(actual: usize, min: Option<usize>, max: Option<usize>) -> bool {
+    let above_min = match min {
+        Some(min) => actual >= min,
+        None => true,
+    };
+
+    let below_max = match max {
+        Some(max) => actual <= max,
+        None => true,
+    };
+
+    above_min && below_max
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds() {
+        assert!(is_valid_item_count(0, None, None));
+        assert!(is_valid_item_count(2, Some(1), Some(2)));
+        assert!(!is_valid_item_count(0, Some(1), None));
+        assert!(!is_valid_item_count(3, None, Some(2)));
+
+        assert_eq!(
+            *ensure_item_count(0, Some(1), Some(2))
+                .expect_err("should be out of bounds")
+                .current_context(),
+            ItemCountError::TooFew {
+                expected: 1,
+                actual: 0
+            }
+        );
+        assert_eq!(
+            *ensure_item_count(3, Some(1), Some(2))
+                .expect_err("should be out of bounds")
+                .current_context(),
+            ItemCountError::TooMany {
+                expected: 2,
+                actual: 3
+            }
+        );
+        assert!(ensure_item_count(1, Some(1), Some(2)).is_ok());
+    }
+}
@@ -11,23 +11,138 @@ use core::fmt;
 
 use error_stack::{Context, Result};
 use serde::Serialize;
+use serde_json::value::RawValue;
 pub use type_system::url::{BaseUrl, VersionedUrl};
+use uuid::Uuid;
 
-use crate::entity::{Entity, LinkData};
+use crate::{
+    entity::{Entity, EntityId, EntityMetadata, EntityTemporalMetadata, LinkData},
+    raw::RawEntity,
+};
 
+pub mod builder;
 pub mod entity;
 mod error;
+#[cfg(test)]
+mod fixture;
 mod hierarchy;
+mod items;
+pub mod link;
+mod nullable;
 pub mod path;
 mod polyfill;
+pub mod raw;
 mod serialize;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+pub mod subgraph;
 pub mod types;
 
-pub use error::{GenericEntityError, GenericPropertyError};
+pub use error::{
+    at_field, at_index, ErrorLocation, GenericDataTypeError, GenericEntityError,
+    GenericPropertyError, JsonKind, WireError,
+};
+pub use items::{ensure_item_count, is_valid_item_count, ItemCountError};
+pub use nullable::Nullable;
 pub use polyfill::{fold_iter_reports, fold_tuple_reports};
 
-pub use crate::hierarchy::TypeHierarchyResolution;
-use crate::path::TypePath;
+pub use crate::hierarchy::{is_subtype_of, InheritsFrom};
+use crate::path::{Segments, TypePath};
+
+/// Maximum length of a base URL, as enforced by the type-system crate.
+const MAX_BASE_URL_LENGTH: usize = 2048;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BaseUrlError {
+    Empty,
+    TooLong,
+    MissingTrailingSlash,
+    MissingScheme,
+    MissingHost,
+    ContainsWhitespace,
+}
+
+impl BaseUrlError {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Empty => "base URL must not be empty",
+            Self::TooLong => "base URL must not be longer than 2048 characters",
+            Self::MissingTrailingSlash => "base URL must end with a trailing slash",
+            Self::MissingScheme => "base URL must start with a scheme, e.g. `https://`",
+            Self::MissingHost => "base URL must have a host",
+            Self::ContainsWhitespace => {
+                "base URL must not contain whitespace or control characters"
+            }
+        }
+    }
+}
+
+impl fmt::Display for BaseUrlError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+/// Validate a base URL in a `const` context.
+///
+/// This is a subset of the validation done by [`BaseUrl::new`], which is possible at compile
+/// time: the URL must have a scheme and a host, must end with a trailing slash and must not
+/// contain any whitespace.
+///
+/// # Errors
+///
+/// if the base URL is malformed, see [`BaseUrlError`] for the individual reasons
+pub const fn validate_base_url(url: &str) -> core::result::Result<(), BaseUrlError> {
+    let bytes = url.as_bytes();
+
+    if bytes.is_empty() {
+        return Err(BaseUrlError::Empty);
+    }
+
+    if bytes.len() > MAX_BASE_URL_LENGTH {
+        return Err(BaseUrlError::TooLong);
+    }
+
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index].is_ascii_whitespace() || bytes[index].is_ascii_control() {
+            return Err(BaseUrlError::ContainsWhitespace);
+        }
+
+        index += 1;
+    }
+
+    if bytes[bytes.len() - 1] != b'/' {
+        return Err(BaseUrlError::MissingTrailingSlash);
+    }
+
+    // scheme = ALPHA *( ALPHA / DIGIT / "+" / "-" / "." ), see RFC 3986
+    if !bytes[0].is_ascii_alphabetic() {
+        return Err(BaseUrlError::MissingScheme);
+    }
+
+    let mut index = 1;
+    while index < bytes.len()
+        && (bytes[index].is_ascii_alphanumeric() || matches!(bytes[index], b'+' | b'-' | b'.'))
+    {
+        index += 1;
+    }
+
+    if index + 3 > bytes.len()
+        || bytes[index] != b':'
+        || bytes[index + 1] != b'/'
+        || bytes[index + 2] != b'/'
+    {
+        return Err(BaseUrlError::MissingScheme);
+    }
+
+    if index + 3 == bytes.len() || bytes[index + 3] == b'/' {
+        return Err(BaseUrlError::MissingHost);
+    }
+
+    Ok(())
+}
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub struct BaseUrlRef<'a>(&'a str);
@@ -45,8 +160,40 @@ impl<'a> BaseUrlRef<'a> {
         Self(url)
     }
 
+    /// Create a new URL, which is validated using [`validate_base_url`].
+    ///
+    /// # Errors
+    ///
+    /// if the base URL is malformed
+    pub const fn new(url: &'a str) -> core::result::Result<Self, BaseUrlError> {
+        match validate_base_url(url) {
+            Ok(()) => Ok(Self(url)),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Create a new URL, which is validated using [`validate_base_url`].
+    ///
+    /// If used in a `const` context, an invalid URL results in a compile time error. This is used
+    /// by the [`url`] macro.
+    ///
+    /// # Panics
+    ///
+    /// if the base URL is malformed
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn new_checked(url: &'a str) -> Self {
+        match validate_base_url(url) {
+            Ok(()) => Self(url),
+            Err(error) => panic!("{}", error.as_str()),
+        }
+    }
+
     // cannot implement ToOwned because `AsRef` is not implemented
-    // TODO: compile time fail! ~> const fn validator?
+    /// # Panics
+    ///
+    /// if the URL has been created through [`BaseUrlRef::new_unchecked`] and is not a valid base
+    /// URL
     #[must_use]
     pub fn into_owned(self) -> BaseUrl {
         BaseUrl::new(self.0.to_owned()).expect("invalid Base URL")
@@ -77,6 +224,22 @@ impl<'a> VersionedUrlRef<'a> {
         Self { base, version }
     }
 
+    /// Create a new URL, where the version is validated to be non-zero.
+    ///
+    /// If used in a `const` context, an invalid version results in a compile time error. This is
+    /// used by the [`url`] macro.
+    ///
+    /// # Panics
+    ///
+    /// if the version is `0`
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn new_checked(base: BaseUrlRef<'a>, version: u32) -> Self {
+        assert!(version > 0, "version must be a positive integer");
+
+        Self { base, version }
+    }
+
     #[must_use]
     pub const fn base(&self) -> BaseUrlRef<'a> {
         self.base
@@ -129,21 +292,66 @@ impl<'a> From<&'a VersionedUrl> for VersionedUrlRef<'a> {
     }
 }
 
+/// Create a [`VersionedUrlRef`], which is validated at compile time.
+///
+/// ```
+/// use turbine::{url, VersionedUrlRef};
+///
+/// const URL: VersionedUrlRef<'static> =
+///     url!("https://blockprotocol.org/@blockprotocol/types/data-type/text/" / v / 1);
+/// ```
+///
+/// Malformed URLs, like a missing trailing slash, result in a compile time error:
+///
+/// ```compile_fail
+/// use turbine::{url, VersionedUrlRef};
+///
+/// const URL: VersionedUrlRef<'static> =
+///     url!("https://blockprotocol.org/@blockprotocol/types/data-type/text" / v / 1);
+/// ```
+///
+/// as do versions, which are not positive:
+///
+/// ```compile_fail
+/// use turbine::{url, VersionedUrlRef};
+///
+/// const URL: VersionedUrlRef<'static> =
+///     url!("https://blockprotocol.org/@blockprotocol/types/data-type/text/" / v / 0);
+/// ```
 #[macro_export]
 macro_rules! url {
-    ($base:literal / v / $version:literal) => {
-        $crate::VersionedUrlRef::new_unchecked($crate::BaseUrlRef::new_unchecked($base), $version)
-    };
+    ($base:literal / v / $version:literal) => {{
+        const URL: $crate::VersionedUrlRef<'static> =
+            $crate::VersionedUrlRef::new_checked($crate::BaseUrlRef::new_checked($base), $version);
+
+        URL
+    }};
 }
 
 pub trait TypeUrl {
-    type InheritsFrom: TypeHierarchyResolution;
-
+    /// All types this type inherits from, either directly or indirectly, without duplicates.
+    ///
+    /// The type itself is not included.
+    const ANCESTORS: &'static [VersionedUrlRef<'static>] = &[];
     const ID: VersionedUrlRef<'static>;
+
+    /// Returns `true` if this type is `P` or inherits from `P`, either directly or indirectly.
+    ///
+    /// To require a subtype at compile time use [`InheritsFrom`] instead.
+    #[must_use]
+    fn is_subtype_of<P: TypeUrl + ?Sized>() -> bool {
+        is_subtype_of(Self::ID, Self::ANCESTORS, P::ID)
+    }
 }
 
 pub trait TypeTraverse {
     type Path: TypePath;
+
+    /// Start a typed path at the root of this type.
+    #[must_use]
+    fn path() -> Self::Path {
+        Self::Path::from_segments(Segments::new())
+    }
 }
 
 pub trait TypeRef: TypeUrl + Sized {
@@ -180,6 +388,13 @@ pub trait DataTypeRef<'a>: Serialize + TypeRef {
     ///
     /// if the value is malformed and not of the correct shape
     fn try_from_value(value: &'a serde_json::Value) -> Result<Self, Self::Error>;
+
+    /// Equivalent to [`Self::try_from_value`], but borrows directly from the input buffer.
+    ///
+    /// # Errors
+    ///
+    /// if the value is malformed and not of the correct shape
+    fn try_from_raw(value: &'a RawValue) -> Result<Self, Self::Error>;
 }
 
 pub trait DataTypeMut<'a>: Serialize + TypeMut {
@@ -214,6 +429,13 @@ pub trait PropertyTypeRef<'a>: Serialize + TypeRef {
     ///
     /// if the value is malformed and not of the correct shape
     fn try_from_value(value: &'a serde_json::Value) -> Result<Self, Self::Error>;
+
+    /// Equivalent to [`Self::try_from_value`], but borrows directly from the input buffer.
+    ///
+    /// # Errors
+    ///
+    /// if the value is malformed and not of the correct shape
+    fn try_from_raw(value: &'a RawValue) -> Result<Self, Self::Error>;
 }
 
 pub trait PropertyTypeMut<'a>: Serialize + TypeMut {
@@ -245,6 +467,10 @@ pub trait EntityTypeRef<'a>: Serialize + TypeRef {
     type Error: Context;
 
     fn try_from_entity(value: &'a Entity) -> Option<Result<Self, Self::Error>>;
+
+    /// Equivalent to [`Self::try_from_entity`], but reads the properties directly from the input
+    /// buffer, instead of a materialised [`serde_json::Value`].
+    fn try_from_raw_entity(value: &'a RawEntity<'a>) -> Option<Result<Self, Self::Error>>;
 }
 
 pub trait EntityTypeMut<'a>: Serialize + TypeMut {
@@ -273,7 +499,59 @@ where
 pub trait EntityProperties {
     type Properties: Serialize;
 
+    /// Base URLs of every property known to the type, regardless if they are set or not.
+    const KEYS: &'static [&'static str];
+
     fn properties(&self) -> &Self::Properties;
+
+    /// Serialize the properties into their wire representation, which is keyed by base URL.
+    ///
+    /// # Errors
+    ///
+    /// if the properties cannot be serialized, or do not serialize into an object
+    fn to_entity_properties(&self) -> Result<entity::EntityProperties, WireError> {
+        entity::EntityProperties::from_typed(self.properties())
+    }
+}
+
+/// Access to the metadata of the entity, a typed value has been created from.
+pub trait EntityRecord {
+    fn metadata(&self) -> &EntityMetadata;
+
+    #[must_use]
+    fn entity_id(&self) -> EntityId {
+        self.metadata().record_id.entity_id
+    }
+
+    #[must_use]
+    fn edition_id(&self) -> Uuid {
+        self.metadata().record_id.edition_id
+    }
+
+    #[must_use]
+    fn temporal_versioning(&self) -> &EntityTemporalMetadata {
+        &self.metadata().temporal_versioning
+    }
+
+    #[must_use]
+    fn created_by(&self) -> Uuid {
+        self.metadata().provenance.record_created_by_id
+    }
+
+    #[must_use]
+    fn archived_by(&self) -> Option<Uuid> {
+        self.metadata().provenance.record_archived_by_id
+    }
+
+    #[must_use]
+    fn is_archived(&self) -> bool {
+        self.metadata().archived
+    }
+
+    #[must_use]
+    fn is_draft(&self) -> bool {
+        self.metadata().draft
+    }
 }
 
 pub trait EntityLink {
@@ -293,3 +571,50 @@ where
         Some(<T as EntityLink>::link_data(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_base_url() {
+        assert_eq!(
+            validate_base_url("https://blockprotocol.org/@blockprotocol/types/data-type/text/"),
+            Ok(())
+        );
+        assert_eq!(validate_base_url("http://localhost:3000/"), Ok(()));
+    }
+
+    #[test]
+    fn invalid_base_url() {
+        assert_eq!(validate_base_url(""), Err(BaseUrlError::Empty));
+        assert_eq!(
+            validate_base_url("https://example.com/person"),
+            Err(BaseUrlError::MissingTrailingSlash)
+        );
+        assert_eq!(
+            validate_base_url("example.com/person/"),
+            Err(BaseUrlError::MissingScheme)
+        );
+        assert_eq!(
+            validate_base_url("https:/example.com/person/"),
+            Err(BaseUrlError::MissingScheme)
+        );
+        assert_eq!(
+            validate_base_url("https:///"),
+            Err(BaseUrlError::MissingHost)
+        );
+        assert_eq!(
+            validate_base_url("https://example.com/my person/"),
+            Err(BaseUrlError::ContainsWhitespace)
+        );
+    }
+
+    #[test]
+    fn url_macro() {
+        let url = url!("https://example.com/person/" / v / 2);
+
+        assert_eq!(url.base().as_str(), "https://example.com/person/");
+        assert_eq!(url.version(), 2);
+    }
+}
@@ -0,0 +1,463 @@
+//! Typed navigation over the outgoing links of an entity.
+//!
+//! Code generation emits an [`OutgoingLink`] implementation for every link constraint of an
+//! entity type, which records the allowed destinations and cardinalities. [`links`] uses this
+//! information to resolve all links of a specific type from a [`LinkSource`] and validate them.
+//! A [`LinkIndex`] is a [`LinkSource`] over any collection of entities.
+
+use alloc::vec::Vec;
+
+use error_stack::{Report, Result, ResultExt};
+use hashbrown::HashMap;
+use onlyerror::Error;
+
+use crate::{
+    entity::{Entity, EntityId},
+    fold_iter_reports, EntityTypeRef, GenericEntityError, Type, TypeUrl,
+};
+
+#[derive(Debug, Copy, Clone, Error)]
+pub enum LinkError {
+    #[error("unable to convert link entity")]
+    Link,
+    #[error("unable to find right entity of link")]
+    MissingRightEntity,
+    #[error("right entity of link is not one of the allowed destinations")]
+    InvalidDestination,
+    #[error("unable to convert right entity of link")]
+    Destination,
+    #[error("expected at least {expected} links, found {actual}")]
+    TooFew { expected: usize, actual: usize },
+    #[error("expected at most {expected} links, found {actual}")]
+    TooMany { expected: usize, actual: usize },
+}
+
+/// Entity which is allowed as the right-hand side of a link.
+///
+/// This is implemented for every generated `Ref` entity type, as well as for [`Entity`], which is
+/// used if a link has no destination constraints.
+pub trait LinkDestination<'a>: Sized {
+    /// Returns `None` if the entity is not one of the allowed destinations.
+    fn try_from_entity(entity: &'a Entity) -> Option<Result<Self, GenericEntityError>>;
+}
+
+impl<'a, T> LinkDestination<'a> for T
+where
+    T: EntityTypeRef<'a, Error = GenericEntityError>,
+{
+    fn try_from_entity(entity: &'a Entity) -> Option<Result<Self, GenericEntityError>> {
+        <T as EntityTypeRef<'a>>::try_from_entity(entity)
+    }
+}
+
+impl<'a> LinkDestination<'a> for &'a Entity {
+    fn try_from_entity(entity: &'a Entity) -> Option<Result<Self, GenericEntityError>> {
+        Some(Ok(entity))
+    }
+}
+
+/// Link constraint of an entity type.
+///
+/// `L` is the link entity type, while [`Self::Destination`] describes all entity types that are
+/// allowed as the right-hand side of the link.
+pub trait OutgoingLink<L>: TypeUrl
+where
+    L: TypeUrl,
+{
+    type Destination<'a>: LinkDestination<'a>;
+
+    /// If `true`, links are sorted by their left to right order.
+    const ORDERED: bool;
+
+    const MIN_ITEMS: Option<usize>;
+    const MAX_ITEMS: Option<usize>;
+}
+
+/// A link entity together with the entity it points to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Linked<L, R> {
+    pub link: L,
+    pub right: R,
+}
+
+/// Outgoing links of type `L` of an entity of type `T`, as resolved by [`links`].
+pub type Links<'a, T, L> =
+    Vec<Linked<<L as Type>::Ref<'a>, <T as OutgoingLink<L>>::Destination<'a>>>;
+
+/// Collection of entities, which is able to resolve the outgoing links of an entity.
+pub trait LinkSource {
+    /// Returns every link entity whose left entity is `entity`, together with the right entity,
+    /// if it is part of the source.
+    fn outgoing_links(&self, entity: EntityId) -> Vec<Linked<&Entity, Option<&Entity>>>;
+}
+
+/// Index of a collection of entities by their id and the id of their left entity.
+///
+/// Resolving the outgoing links of an entity through the index only visits the links of that
+/// entity. Building the index visits every entity once, so it should be built once and reused for
+/// every entity whose links are resolved.
+#[derive(Debug, Clone, Default)]
+pub struct LinkIndex<'a> {
+    entities: HashMap<EntityId, &'a Entity>,
+    outgoing: HashMap<EntityId, Vec<&'a Entity>>,
+}
+
+impl<'a> LinkIndex<'a> {
+    pub fn new(entities: impl IntoIterator<Item = &'a Entity>) -> Self {
+        let mut index = Self::default();
+        index.extend(entities);
+        index
+    }
+
+    pub fn insert(&mut self, entity: &'a Entity) {
+        // the first entity with a given id wins, like a linear search would
+        self.entities
+            .entry(entity.metadata.record_id.entity_id)
+            .or_insert(entity);
+
+        if let Some(link_data) = &entity.link_data {
+            self.outgoing
+                .entry(link_data.left_entity_id)
+                .or_default()
+                .push(entity);
+        }
+    }
+}
+
+impl<'a> Extend<&'a Entity> for LinkIndex<'a> {
+    fn extend<T: IntoIterator<Item = &'a Entity>>(&mut self, iter: T) {
+        for entity in iter {
+            self.insert(entity);
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a Entity> for LinkIndex<'a> {
+    fn from_iter<T: IntoIterator<Item = &'a Entity>>(iter: T) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl LinkSource for LinkIndex<'_> {
+    fn outgoing_links(&self, entity: EntityId) -> Vec<Linked<&Entity, Option<&Entity>>> {
+        let Some(links) = self.outgoing.get(&entity) else {
+            return Vec::new();
+        };
+
+        links
+            .iter()
+            .map(|&link| {
+                let right = link
+                    .link_data
+                    .as_ref()
+                    .and_then(|link_data| self.entities.get(&link_data.right_entity_id))
+                    .copied();
+
+                Linked { link, right }
+            })
+            .collect()
+    }
+}
+
+fn convert<'a, L, R>(
+    Linked { link, right }: Linked<&'a Entity, Option<&'a Entity>>,
+) -> Option<Result<Linked<L, R>, LinkError>>
+where
+    L: EntityTypeRef<'a>,
+    R: LinkDestination<'a>,
+{
+    let link = match <L as EntityTypeRef<'a>>::try_from_entity(link)? {
+        Ok(link) => link,
+        Err(error) => return Some(Err(error.change_context(LinkError::Link))),
+    };
+
+    let Some(right) = right else {
+        return Some(Err(Report::new(LinkError::MissingRightEntity)));
+    };
+
+    let right = R::try_from_entity(right).map_or_else(
+        || Err(Report::new(LinkError::InvalidDestination)),
+        |right| right.change_context(LinkError::Destination),
+    );
+
+    Some(right.map(|right| Linked { link, right }))
+}
+
+/// Resolve all outgoing links of type `L` of the entity with the id `entity`, which is of type `T`.
+///
+/// Link entities that are not of type `L` are ignored, while every link of type `L` must point to
+/// one of the destinations declared in `T`. If the link constraint is ordered, links are sorted by
+/// their left to right order, links without an order are placed last.
+///
+/// # Errors
+///
+/// - [`LinkError::Link`], if a link entity of type `L` is malformed
+/// - [`LinkError::MissingRightEntity`], if the right entity is not part of the `source`
+/// - [`LinkError::InvalidDestination`], if the right entity is not an allowed destination
+/// - [`LinkError::Destination`], if the right entity is malformed
+/// - [`LinkError::TooFew`] or [`LinkError::TooMany`], if the cardinality constraints are violated
+pub fn links<'a, T, L, S>(entity: EntityId, source: &'a S) -> Result<Links<'a, T, L>, LinkError>
+where
+    T: OutgoingLink<L>,
+    L: Type + 'a,
+    L::Ref<'a>: EntityTypeRef<'a>,
+    S: LinkSource + ?Sized,
+{
+    let mut outgoing = source.outgoing_links(entity);
+
+    if T::ORDERED {
+        // `None` is ordered before `Some`, which means we need to sort them to the back manually
+        outgoing.sort_by_key(|Linked { link, .. }| {
+            let order = link
+                .link_data
+                .as_ref()
+                .and_then(|link_data| link_data.order.left_to_right);
+
+            (order.is_none(), order)
+        });
+    }
+
+    let links = outgoing
+        .into_iter()
+        .filter_map(convert::<L::Ref<'a>, T::Destination<'a>>);
+
+    let links = fold_iter_reports(links)?;
+
+    let actual = links.len();
+
+    if let Some(expected) = T::MIN_ITEMS {
+        if actual < expected {
+            return Err(Report::new(LinkError::TooFew { expected, actual }));
+        }
+    }
+
+    if let Some(expected) = T::MAX_ITEMS {
+        if actual > expected {
+            return Err(Report::new(LinkError::TooMany { expected, actual }));
+        }
+    }
+
+    Ok(links)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use serde::Serialize;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{fixture, url, RawEntity, TypeMut, TypeRef, VersionedUrlRef};
+
+    const ALICE: &str = fixture::ENTITY_ID;
+    const BOB: &str = "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e~1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c5d";
+    const CAROL: &str = "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e~2b3c4d5e-6f7a-4b8c-9d0e-1f2a3b4c5d6e";
+
+    const KNOWS: &str = "https://example.com/entity-type/knows/v/1";
+
+    const KNOWS_ID: VersionedUrlRef<'static> =
+        url!("https://example.com/entity-type/knows/" / v / 1);
+
+    struct Person;
+
+    impl TypeUrl for Person {
+        const ID: VersionedUrlRef<'static> =
+            url!("https://example.com/entity-type/person/" / v / 1);
+    }
+
+    impl OutgoingLink<Knows> for Person {
+        type Destination<'a> = &'a Entity;
+
+        const MAX_ITEMS: Option<usize> = Some(2);
+        const MIN_ITEMS: Option<usize> = None;
+        const ORDERED: bool = true;
+    }
+
+    struct Knows(Entity);
+
+    #[derive(Debug, Serialize)]
+    struct KnowsRef<'a>(&'a Entity);
+
+    #[derive(Serialize)]
+    struct KnowsMut<'a>(&'a mut Entity);
+
+    impl TypeUrl for Knows {
+        const ID: VersionedUrlRef<'static> = KNOWS_ID;
+    }
+
+    impl TypeUrl for KnowsRef<'_> {
+        const ID: VersionedUrlRef<'static> = KNOWS_ID;
+    }
+
+    impl TypeUrl for KnowsMut<'_> {
+        const ID: VersionedUrlRef<'static> = KNOWS_ID;
+    }
+
+    impl Type for Knows {
+        type Mut<'a> = KnowsMut<'a> where Self: 'a;
+        type Ref<'a> = KnowsRef<'a> where Self: 'a;
+
+        fn as_mut(&mut self) -> Self::Mut<'_> {
+            KnowsMut(&mut self.0)
+        }
+
+        fn as_ref(&self) -> Self::Ref<'_> {
+            KnowsRef(&self.0)
+        }
+    }
+
+    impl TypeRef for KnowsRef<'_> {
+        type Owned = Knows;
+
+        fn into_owned(self) -> Self::Owned {
+            Knows(self.0.clone())
+        }
+    }
+
+    impl TypeMut for KnowsMut<'_> {
+        type Owned = Knows;
+
+        fn into_owned(self) -> Self::Owned {
+            Knows(self.0.clone())
+        }
+    }
+
+    impl<'a> EntityTypeRef<'a> for KnowsRef<'a> {
+        type Error = GenericEntityError;
+
+        fn try_from_entity(value: &'a Entity) -> Option<Result<Self, Self::Error>> {
+            value
+                .metadata
+                .entity_type_ids
+                .contains(KNOWS_ID)
+                .then_some(Ok(Self(value)))
+        }
+
+        fn try_from_raw_entity(_: &'a RawEntity<'a>) -> Option<Result<Self, Self::Error>> {
+            None
+        }
+    }
+
+    fn person(id: &str) -> Entity {
+        fixture::entity().entity_id(id).build()
+    }
+
+    fn knows(id: &str, left: &str, right: &str, order: Option<i32>) -> Entity {
+        fixture::entity()
+            .entity_id(id)
+            .entity_type(KNOWS)
+            .link_data(json!({
+                "leftEntityId": left,
+                "rightEntityId": right,
+                "leftToRightOrder": order,
+            }))
+            .build()
+    }
+
+    fn entity_id(id: &str) -> EntityId {
+        serde_json::from_value(json!(id)).expect("should be a valid entity id")
+    }
+
+    fn right_entity_ids(links: &Links<Person, Knows>) -> Vec<EntityId> {
+        links
+            .iter()
+            .map(|linked| linked.right.metadata.record_id.entity_id)
+            .collect()
+    }
+
+    fn people() -> Vec<Entity> {
+        vec![person(ALICE), person(BOB), person(CAROL)]
+    }
+
+    #[test]
+    fn both_directions() {
+        let mut source = people();
+        source.extend([
+            knows(
+                "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e~00000000-0000-4000-8000-000000000001",
+                ALICE,
+                CAROL,
+                None,
+            ),
+            knows(
+                "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e~00000000-0000-4000-8000-000000000002",
+                ALICE,
+                BOB,
+                Some(0),
+            ),
+            knows(
+                "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e~00000000-0000-4000-8000-000000000003",
+                BOB,
+                ALICE,
+                None,
+            ),
+        ]);
+
+        let index = LinkIndex::new(&source);
+
+        // ordered links come first, links without an order are placed last
+        let alice =
+            links::<Person, Knows, _>(entity_id(ALICE), &index).expect("should be valid links");
+        assert_eq!(right_entity_ids(&alice), [entity_id(BOB), entity_id(CAROL)]);
+
+        // only the link with `BOB` on the left-hand side is followed
+        let bob = links::<Person, Knows, _>(entity_id(BOB), &index).expect("should be valid links");
+        assert_eq!(right_entity_ids(&bob), [entity_id(ALICE)]);
+
+        let carol =
+            links::<Person, Knows, _>(entity_id(CAROL), &index).expect("should be valid links");
+        assert!(carol.is_empty());
+    }
+
+    #[test]
+    fn missing_right_entity() {
+        let mut source = people();
+        source.retain(|entity| entity.metadata.record_id.entity_id != entity_id(BOB));
+        source.push(knows(
+            "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e~00000000-0000-4000-8000-000000000001",
+            ALICE,
+            BOB,
+            None,
+        ));
+
+        let error = links::<Person, Knows, _>(entity_id(ALICE), &LinkIndex::new(&source))
+            .expect_err("right entity should be missing");
+        assert!(matches!(
+            error.current_context(),
+            LinkError::MissingRightEntity
+        ));
+    }
+
+    #[test]
+    fn too_many() {
+        let mut source = people();
+        source.extend([
+            knows(
+                "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e~00000000-0000-4000-8000-000000000001",
+                ALICE,
+                BOB,
+                None,
+            ),
+            knows(
+                "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e~00000000-0000-4000-8000-000000000002",
+                ALICE,
+                CAROL,
+                None,
+            ),
+            knows(
+                "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e~00000000-0000-4000-8000-000000000003",
+                ALICE,
+                ALICE,
+                None,
+            ),
+        ]);
+
+        let error = links::<Person, Knows, _>(entity_id(ALICE), &LinkIndex::new(&source))
+            .expect_err("should have too many links");
+        assert!(matches!(error.current_context(), LinkError::TooMany {
+            expected: 2,
+            actual: 3
+        }));
+    }
+}
@@ -0,0 +1,134 @@
+use core::ops::Deref;
+
+use serde::{Serialize, Serializer};
+
+/// Value of an optional property, whose property type accepts `null`.
+///
+/// Unlike `Option<T>`, this distinguishes between a property that is missing and a property
+/// that is explicitly set to `null`, which is required for partial updates, where `null` clears
+/// the value, while a missing property leaves it as is.
+///
+/// [`Self::Absent`] is skipped during serialization, as long as the field is annotated with
+/// `#[serde(skip_serializing_if = "Nullable::is_absent")]`, [`Self::Null`] is serialized as
+/// `null`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Nullable<T> {
+    #[default]
+    Absent,
+    Null,
+    Value(T),
+}
+
+impl<T> Nullable<T> {
+    #[must_use]
+    pub const fn is_absent(&self) -> bool {
+        matches!(self, Self::Absent)
+    }
+
+    #[must_use]
+    pub const fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    #[must_use]
+    pub const fn is_value(&self) -> bool {
+        matches!(self, Self::Value(_))
+    }
+
+    pub const fn as_ref(&self) -> Nullable<&T> {
+        match self {
+            Self::Absent => Nullable::Absent,
+            Self::Null => Nullable::Null,
+            Self::Value(value) => Nullable::Value(value),
+        }
+    }
+
+    pub const fn as_mut(&mut self) -> Nullable<&mut T> {
+        match self {
+            Self::Absent => Nullable::Absent,
+            Self::Null => Nullable::Null,
+            Self::Value(value) => Nullable::Value(value),
+        }
+    }
+
+    pub fn as_deref(&self) -> Nullable<&T::Target>
+    where
+        T: Deref,
+    {
+        self.as_ref().map(|value| &**value)
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Nullable<U> {
+        match self {
+            Self::Absent => Nullable::Absent,
+            Self::Null => Nullable::Null,
+            Self::Value(value) => Nullable::Value(f(value)),
+        }
+    }
+
+    /// Collapse into an `Option<T>`, both [`Self::Absent`] and [`Self::Null`] become `None`.
+    pub fn value(self) -> Option<T> {
+        match self {
+            Self::Absent | Self::Null => None,
+            Self::Value(value) => Some(value),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Nullable<T> {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Absent, Self::Value)
+    }
+}
+
+impl<T: Serialize> Serialize for Nullable<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Absent | Self::Null => serializer.serialize_none(),
+            Self::Value(value) => value.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use serde_json::json;
+
+    use crate::Nullable;
+
+    #[derive(Serialize)]
+    struct Properties {
+        #[serde(skip_serializing_if = "Nullable::is_absent")]
+        name: Nullable<&'static str>,
+    }
+
+    #[test]
+    fn serialize() {
+        let absent = Properties {
+            name: Nullable::Absent,
+        };
+        let null = Properties {
+            name: Nullable::Null,
+        };
+        let value = Properties {
+            name: Nullable::Value("Alice"),
+        };
+
+        assert_eq!(
+            serde_json::to_value(absent).expect("should be serializable"),
+            json!({})
+        );
+        assert_eq!(
+            serde_json::to_value(null).expect("should be serializable"),
+            json!({"name": null})
+        );
+        assert_eq!(
+            serde_json::to_value(value).expect("should be serializable"),
+            json!({"name": "Alice"})
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use alloc::{borrow::Cow, boxed::Box, vec::Vec};
-use core::slice;
+use core::{marker::PhantomData, slice};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Segment<'a> {
@@ -69,16 +69,34 @@ impl<'a> Path<'a> {
     pub const fn new_linked(path: Link<'a>) -> Self {
         Self::Link(path)
     }
+
+    #[must_use]
+    pub fn iter(&self) -> PathIterator<'_, 'a> {
+        match self {
+            Self::Borrowed(slice) => PathIterator::Slice(slice.iter()),
+            Self::Owned(owned) => PathIterator::Slice(owned.iter()),
+            Self::Link(link) => PathIterator::Linked(link),
+        }
+    }
 }
 
-pub enum PathIterator<'a> {
-    Slice(slice::Iter<'a, Segment<'a>>),
-    Linked(&'a Link<'a>),
+impl<'p, 'a> IntoIterator for &'p Path<'a> {
+    type IntoIter = PathIterator<'p, 'a>;
+    type Item = &'p Segment<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub enum PathIterator<'p, 'a> {
+    Slice(slice::Iter<'p, Segment<'a>>),
+    Linked(&'p Link<'a>),
     Empty,
 }
 
-impl<'a> Iterator for PathIterator<'a> {
-    type Item = &'a Segment<'a>;
+impl<'p, 'a> Iterator for PathIterator<'p, 'a> {
+    type Item = &'p Segment<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
         match self {
@@ -103,12 +121,88 @@ impl<'a> Iterator for PathIterator<'a> {
     }
 }
 
-pub trait TypePath {
+/// Segments collected while traversing a type, used by the generated path builders.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Segments(Vec<Segment<'static>>);
+
+impl Segments {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    #[must_use]
+    pub fn push_field(mut self, field: &'static str) -> Self {
+        self.0.push(Segment::Field(Cow::Borrowed(field)));
+        self
+    }
+
+    #[must_use]
+    pub fn push_index(mut self, index: usize) -> Self {
+        self.0.push(Segment::Index(index));
+        self
+    }
+
+    #[must_use]
+    pub fn into_path(self) -> Path<'static> {
+        Path::new_owned(self.0)
+    }
+}
+
+/// Typed path into a type, every step is checked against the schema at compile time.
+pub trait TypePath: Sized {
+    /// Continue the path at the given segments.
+    fn from_segments(segments: Segments) -> Self;
+
     fn path(self) -> Path<'static>;
 }
 
+/// Path to an array of `T`, where a single item can be selected through [`Self::index`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArrayPath<T> {
+    segments: Segments,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: TypePath> ArrayPath<T> {
+    #[must_use]
+    pub fn index(self, index: usize) -> T {
+        T::from_segments(self.segments.push_index(index))
+    }
+}
+
+impl<T: TypePath> TypePath for ArrayPath<T> {
+    fn from_segments(segments: Segments) -> Self {
+        Self {
+            segments,
+            _marker: PhantomData,
+        }
+    }
+
+    fn path(self) -> Path<'static> {
+        self.segments.into_path()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::types::data::DataTypePath;
+
     #[test]
     fn compile() {}
+
+    #[test]
+    fn array() {
+        let path = ArrayPath::<DataTypePath>::from_segments(Segments::new().push_field("a"))
+            .index(2)
+            .path();
+
+        assert_eq!(path.iter().cloned().collect::<Vec<_>>(), vec![
+            Segment::Field(Cow::Borrowed("a")),
+            Segment::Index(2)
+        ]);
+    }
 }
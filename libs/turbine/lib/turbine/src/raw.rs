@@ -0,0 +1,209 @@
+//! Borrowed representation of entities, which is backed by the input buffer.
+//!
+//! Instead of materialising a [`Value`] tree for every property, values are kept as
+//! [`RawValue`], which is only validated, but not parsed, and converted once they are accessed.
+//! Strings without escape sequences are borrowed directly from the input, only strings containing
+//! escape sequences are unescaped into an owned string.
+
+use alloc::{borrow::Cow, collections::BTreeMap, vec::Vec};
+use core::{
+    fmt::{self, Formatter},
+    marker::PhantomData,
+};
+
+use error_stack::{Report, Result};
+use hashbrown::HashMap;
+use serde::{
+    de::{MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use serde_json::{value::RawValue, Number, Value};
+
+use crate::{
+    entity::{Entity, EntityMetadata, EntityProperties, LinkData},
+    JsonKind, WireError,
+};
+
+/// String, which is borrowed from the input if it does not contain any escape sequences.
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct Str<'a>(#[serde(borrow)] Cow<'a, str>);
+
+#[derive(Debug, Clone, Default)]
+pub struct RawProperties<'a>(pub HashMap<Cow<'a, str>, &'a RawValue>);
+
+impl<'a> RawProperties<'a> {
+    #[must_use]
+    pub const fn properties(&self) -> &HashMap<Cow<'a, str>, &'a RawValue> {
+        &self.0
+    }
+
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&'a RawValue> {
+        self.0.get(key).copied()
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for RawProperties<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PropertiesVisitor<'a>(PhantomData<RawProperties<'a>>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for PropertiesVisitor<'a> {
+            type Value = RawProperties<'a>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("an object")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> core::result::Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut properties = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+
+                while let Some((Str(key), value)) = map.next_entry::<Str<'a>, &'a RawValue>()? {
+                    properties.insert(key, value);
+                }
+
+                Ok(RawProperties(properties))
+            }
+        }
+
+        deserializer.deserialize_map(PropertiesVisitor(PhantomData))
+    }
+}
+
+/// Entity, which borrows its properties from the input buffer.
+///
+/// Use [`EntityTypeRef::try_from_raw_entity`] to convert it into a typed entity, which reads
+/// values straight out of the input.
+///
+/// Unlike [`Entity`], fields which are unknown to this version of turbine are ignored, serde cannot
+/// borrow a [`RawValue`] from a struct which collects unknown fields.
+///
+/// [`EntityTypeRef::try_from_raw_entity`]: crate::EntityTypeRef::try_from_raw_entity
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawEntity<'a> {
+    #[serde(borrow)]
+    pub properties: RawProperties<'a>,
+    #[serde(default)]
+    pub link_data: Option<LinkData>,
+    pub metadata: EntityMetadata,
+}
+
+impl RawEntity<'_> {
+    /// Materialise the entity, parsing every property into a [`Value`].
+    ///
+    /// # Errors
+    ///
+    /// if a property cannot be parsed, e.g. because it is nested too deeply
+    pub fn into_entity(self) -> Result<Entity, WireError> {
+        let properties = self
+            .properties
+            .0
+            .into_iter()
+            .map(|(key, value)| {
+                to_value(value)
+                    .map(|value| (key.into_owned(), value))
+                    .ok_or_else(|| Report::new(WireError::Deserialize))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Entity {
+            properties: EntityProperties(properties),
+            link_data: self.link_data,
+            metadata: self.metadata,
+            additional: BTreeMap::new(),
+        })
+    }
+}
+
+/// Kind of the raw value, determined without parsing the value.
+#[must_use]
+pub fn kind(value: &RawValue) -> JsonKind {
+    // a `RawValue` is always valid JSON without surrounding whitespace
+    match value.get().as_bytes().first() {
+        Some(b'n') => JsonKind::Null,
+        Some(b't' | b'f') => JsonKind::Bool,
+        Some(b'"') => JsonKind::String,
+        Some(b'[') => JsonKind::Array,
+        Some(b'{') => JsonKind::Object,
+        _ => JsonKind::Number,
+    }
+}
+
+/// Borrow the string, only allocates if the string contains escape sequences.
+#[must_use]
+pub fn string(value: &RawValue) -> Option<Cow<'_, str>> {
+    serde_json::from_str::<Str>(value.get())
+        .ok()
+        .map(|Str(value)| value)
+}
+
+/// Parse the number, without parsing any other kind of value.
+#[must_use]
+pub fn number(value: &RawValue) -> Option<Number> {
+    if kind(value) != JsonKind::Number {
+        return None;
+    }
+
+    serde_json::from_str(value.get()).ok()
+}
+
+/// Split an object into its raw values, without parsing them.
+#[must_use]
+pub fn object(value: &RawValue) -> Option<RawProperties<'_>> {
+    serde_json::from_str(value.get()).ok()
+}
+
+/// Split an array into its raw values, without parsing them.
+#[must_use]
+pub fn array(value: &RawValue) -> Option<Vec<&RawValue>> {
+    serde_json::from_str(value.get()).ok()
+}
+
+/// Parse the raw value into a [`Value`].
+///
+/// Returns `None` if the value is nested deeper than the recursion limit of `serde_json`.
+#[must_use]
+pub fn to_value(value: &RawValue) -> Option<Value> {
+    serde_json::from_str(value.get()).ok()
+}
+
+/// Parse the raw value for the purpose of error reporting.
+pub(crate) fn to_value_lossy(value: &RawValue) -> Value {
+    to_value(value).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn borrowed() {
+        let input = r#"{"a": "plain", "b": "esc\"aped", "c": [1, {"d": null}]}"#;
+        let properties: RawProperties<'_> =
+            serde_json::from_str(input).expect("should be valid properties");
+
+        let a = string(properties.get("a").expect("should exist")).expect("should be a string");
+        assert!(matches!(a, Cow::Borrowed("plain")));
+
+        let b = string(properties.get("b").expect("should exist")).expect("should be a string");
+        assert!(matches!(b, Cow::Owned(_)));
+        assert_eq!(b, "esc\"aped");
+
+        let c = array(properties.get("c").expect("should exist")).expect("should be an array");
+        assert_eq!(c.len(), 2);
+        assert_eq!(kind(c[0]), JsonKind::Number);
+        assert_eq!(number(c[0]), Some(Number::from(1)));
+        assert_eq!(number(properties.get("a").expect("should exist")), None);
+        assert_eq!(kind(c[1]), JsonKind::Object);
+        assert_eq!(to_value(c[1]), Some(json!({"d": null})));
+    }
+}
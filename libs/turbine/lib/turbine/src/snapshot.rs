@@ -0,0 +1,225 @@
+//! Compact binary snapshots of entities, used to cache exports on disk.
+//!
+//! A snapshot consists of a fixed header followed by the CBOR encoded value:
+//!
+//! | offset | size | content                                 |
+//! |--------|------|-----------------------------------------|
+//! | 0      | 4    | magic bytes `TBSN`                      |
+//! | 4      | 2    | format version, little endian           |
+//! | 6      | 1    | kind of the encoded value, see [`Kind`] |
+//!
+//! The encoding uses the same serde implementations as the JSON representation, which means that
+//! revision ids and temporal metadata are stored with their full precision and collections of
+//! revisions keep their order.
+
+use alloc::{format, vec::Vec};
+
+use error_stack::{Report, Result};
+use onlyerror::Error;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::entity::{Entity, EntityVertex};
+
+const MAGIC: [u8; 4] = *b"TBSN";
+const HEADER_LEN: usize = 7;
+
+/// Version of the snapshot format, snapshots of a different version are rejected.
+pub const VERSION: u16 = 1;
+
+#[derive(Debug, Copy, Clone, Error)]
+pub enum SnapshotError {
+    #[error("unable to encode snapshot")]
+    Encode,
+    #[error("unable to decode snapshot")]
+    Decode,
+    #[error("input is not a snapshot")]
+    InvalidHeader,
+    #[error("snapshot has version {0}, expected version {VERSION}")]
+    UnsupportedVersion(u16),
+    #[error("snapshot contains {found:?}, expected {expected:?}")]
+    UnexpectedKind { expected: Kind, found: u8 },
+}
+
+/// Kind of the value stored in a snapshot.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Kind {
+    Entity = 0,
+    EntityVertex = 1,
+    Entities = 2,
+    EntityVertices = 3,
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Values which can be stored in a snapshot.
+pub trait Snapshot: Serialize + DeserializeOwned + sealed::Sealed {
+    const KIND: Kind;
+}
+
+macro_rules! impl_snapshot {
+    ($($ty:ty => $kind:ident),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl Snapshot for $ty {
+                const KIND: Kind = Kind::$kind;
+            }
+        )*
+    };
+}
+
+impl_snapshot!(
+    Entity => Entity,
+    EntityVertex => EntityVertex,
+    Vec<Entity> => Entities,
+    Vec<EntityVertex> => EntityVertices
+);
+
+/// Encode `value` into a snapshot.
+///
+/// # Errors
+///
+/// if the value cannot be serialized
+pub fn encode<T: Snapshot>(value: &T) -> Result<Vec<u8>, SnapshotError> {
+    let mut output = Vec::new();
+    output.extend_from_slice(&MAGIC);
+    output.extend_from_slice(&VERSION.to_le_bytes());
+    output.push(T::KIND as u8);
+
+    ciborium::ser::into_writer(value, &mut output).map_err(|error| {
+        Report::new(SnapshotError::Encode).attach_printable(format!("{error:?}"))
+    })?;
+
+    Ok(output)
+}
+
+/// Decode a snapshot previously created by [`encode`].
+///
+/// # Errors
+///
+/// - if the header is missing or malformed
+/// - if the snapshot was created by a different version of the format
+/// - if the snapshot contains a different kind of value than `T`
+/// - if the value cannot be deserialized
+pub fn decode<T: Snapshot>(bytes: &[u8]) -> Result<T, SnapshotError> {
+    if bytes.len() < HEADER_LEN || bytes[..4] != MAGIC {
+        return Err(Report::new(SnapshotError::InvalidHeader));
+    }
+
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    if version != VERSION {
+        return Err(Report::new(SnapshotError::UnsupportedVersion(version)));
+    }
+
+    let kind = bytes[6];
+    if kind != T::KIND as u8 {
+        return Err(Report::new(SnapshotError::UnexpectedKind {
+            expected: T::KIND,
+            found: kind,
+        }));
+    }
+
+    ciborium::de::from_reader(&bytes[HEADER_LEN..])
+        .map_err(|error| Report::new(SnapshotError::Decode).attach_printable(format!("{error:?}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeMap, vec};
+
+    use serde_json::json;
+    use time::OffsetDateTime;
+
+    use super::*;
+    use crate::{entity::RevisionId, fixture};
+
+    fn entity(name: &str, transaction_time: &str) -> Entity {
+        fixture::entity()
+            .properties(json!({
+                "https://example.com/property-type/name/": name,
+                "https://example.com/property-type/age/": 42,
+                "https://example.com/property-type/tags/": ["a", {"b": null}, 1.5]
+            }))
+            .decision_time(json!({
+                "start": { "kind": "inclusive", "limit": "2023-01-01T00:00:00.123456789Z" },
+                "end": { "kind": "exclusive", "limit": "2024-01-01T00:00:00Z" }
+            }))
+            .transaction_time(fixture::since(transaction_time))
+            .build()
+    }
+
+    fn revision(time: &str) -> RevisionId {
+        RevisionId::from(
+            OffsetDateTime::parse(time, &time::format_description::well_known::Rfc3339)
+                .expect("should be a valid timestamp"),
+        )
+    }
+
+    #[test]
+    fn entity_round_trip() {
+        let entity = entity("Alice", "2023-01-01T00:00:00.000000001Z");
+
+        let bytes = encode(&entity).expect("should be able to encode");
+        let decoded: Entity = decode(&bytes).expect("should be able to decode");
+
+        assert_eq!(decoded, entity);
+    }
+
+    #[test]
+    fn vertex_round_trip() {
+        let revisions = [
+            "2023-01-01T00:00:00.000000002Z",
+            "2023-01-01T00:00:00.000000001Z",
+            "2022-12-31T23:59:59Z",
+        ];
+
+        let vertex = EntityVertex(
+            revisions
+                .into_iter()
+                .map(|time| (revision(time), entity("Alice", time)))
+                .collect::<BTreeMap<_, _>>(),
+        );
+
+        let vertices = vec![vertex.clone(), vertex];
+
+        let bytes = encode(&vertices).expect("should be able to encode");
+        let decoded: Vec<EntityVertex> = decode(&bytes).expect("should be able to decode");
+
+        assert_eq!(decoded, vertices);
+        assert_eq!(
+            decoded[0].latest_version(),
+            revision("2023-01-01T00:00:00.000000002Z")
+        );
+    }
+
+    #[test]
+    fn header() {
+        let entity = entity("Alice", "2023-01-01T00:00:00Z");
+        let mut bytes = encode(&entity).expect("should be able to encode");
+
+        let error = decode::<EntityVertex>(&bytes).expect_err("should reject other kinds");
+        assert!(matches!(
+            error.current_context(),
+            SnapshotError::UnexpectedKind {
+                expected: Kind::EntityVertex,
+                found: 0
+            }
+        ));
+
+        bytes[4] = 2;
+        let error = decode::<Entity>(&bytes).expect_err("should reject other versions");
+        assert!(matches!(
+            error.current_context(),
+            SnapshotError::UnsupportedVersion(2)
+        ));
+
+        let error = decode::<Entity>(b"{}").expect_err("should reject JSON");
+        assert!(matches!(
+            error.current_context(),
+            SnapshotError::InvalidHeader
+        ));
+    }
+}
@@ -0,0 +1,543 @@
+//! Model of the subgraph returned by the queries of the HASH graph.
+//!
+//! A subgraph consists of the `roots` of the query, all `vertices` that were reached while
+//! resolving the query, and the `edges` between them. Ontology types are identified by their
+//! [`VersionedUrl`], while entities are grouped into an [`EntityVertex`] containing every
+//! revision.
+
+mod edge;
+mod temporal;
+mod vertex;
+
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+use hashbrown::{HashMap, HashSet};
+use onlyerror::Error;
+use serde::Deserialize;
+use type_system::url::{BaseUrl, VersionedUrl};
+
+pub use self::{
+    edge::{Edge, EdgeEndpoint, EdgeKind},
+    temporal::{
+        PinnedTemporalAxis, QueryTemporalAxes, SubgraphTemporalAxes, TemporalBound, TimeAxis,
+        VariableTemporalAxis,
+    },
+    vertex::{OntologyKind, OntologyVertex, VertexId},
+};
+use crate::{
+    entity::{Entity, EntityId, EntityVertex, RevisionId},
+    link::{LinkSource, Linked},
+};
+
+#[derive(Debug, Copy, Clone, Error)]
+pub enum SubgraphError {
+    #[error("invalid base URL")]
+    InvalidBaseUrl,
+    #[error("invalid revision id")]
+    InvalidRevisionId,
+    #[error("vertex kind does not match its identifier")]
+    VertexKind,
+}
+
+fn versioned_url(base_id: String, version: u32) -> Result<VersionedUrl, SubgraphError> {
+    let base_url = BaseUrl::new(base_id).map_err(|_error| SubgraphError::InvalidBaseUrl)?;
+
+    Ok(VersionedUrl { base_url, version })
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum RawVertex {
+    DataType { inner: OntologyVertex },
+    PropertyType { inner: OntologyVertex },
+    EntityType { inner: OntologyVertex },
+    Entity { inner: Box<Entity> },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSubgraph {
+    roots: Vec<VertexId>,
+    vertices: HashMap<String, HashMap<String, RawVertex>>,
+    edges: HashMap<String, HashMap<String, Vec<Edge>>>,
+    temporal_axes: SubgraphTemporalAxes,
+}
+
+/// Subgraph as returned by the HASH graph.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawSubgraph")]
+pub struct Subgraph {
+    roots: Vec<VertexId>,
+
+    data_types: HashMap<VersionedUrl, OntologyVertex>,
+    property_types: HashMap<VersionedUrl, OntologyVertex>,
+    entity_types: HashMap<VersionedUrl, OntologyVertex>,
+    entities: HashMap<EntityId, EntityVertex>,
+
+    edges: HashMap<VertexId, Vec<Edge>>,
+
+    temporal_axes: SubgraphTemporalAxes,
+}
+
+impl TryFrom<RawSubgraph> for Subgraph {
+    type Error = SubgraphError;
+
+    fn try_from(value: RawSubgraph) -> Result<Self, Self::Error> {
+        let mut data_types = HashMap::new();
+        let mut property_types = HashMap::new();
+        let mut entity_types = HashMap::new();
+        let mut entities = HashMap::new();
+
+        for (base_id, revisions) in value.vertices {
+            let mut editions = BTreeMap::new();
+
+            for (revision_id, vertex) in revisions {
+                let id = VertexId::parse(&base_id, &revision_id)?;
+
+                match (id, vertex) {
+                    (VertexId::Ontology(url), RawVertex::DataType { inner }) => {
+                        data_types.insert(url, inner);
+                    }
+                    (VertexId::Ontology(url), RawVertex::PropertyType { inner }) => {
+                        property_types.insert(url, inner);
+                    }
+                    (VertexId::Ontology(url), RawVertex::EntityType { inner }) => {
+                        entity_types.insert(url, inner);
+                    }
+                    (VertexId::Entity { revision_id, .. }, RawVertex::Entity { inner }) => {
+                        editions.insert(revision_id, *inner);
+                    }
+                    (VertexId::Ontology(_), RawVertex::Entity { .. })
+                    | (VertexId::Entity { .. }, _) => return Err(SubgraphError::VertexKind),
+                }
+            }
+
+            let entity_id = editions
+                .values()
+                .next()
+                .map(|entity: &Entity| entity.metadata.record_id.entity_id);
+
+            if let Some(entity_id) = entity_id {
+                entities.insert(entity_id, EntityVertex(editions));
+            }
+        }
+
+        let edges = value
+            .edges
+            .into_iter()
+            .flat_map(|(base_id, revisions)| {
+                revisions.into_iter().map(move |(revision_id, edges)| {
+                    VertexId::parse(&base_id, &revision_id).map(|id| (id, edges))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            roots: value.roots,
+            data_types,
+            property_types,
+            entity_types,
+            entities,
+            edges,
+            temporal_axes: value.temporal_axes,
+        })
+    }
+}
+
+impl Subgraph {
+    #[must_use]
+    pub fn roots(&self) -> &[VertexId] {
+        &self.roots
+    }
+
+    #[must_use]
+    pub const fn temporal_axes(&self) -> &SubgraphTemporalAxes {
+        &self.temporal_axes
+    }
+
+    #[must_use]
+    pub fn data_type(&self, url: &VersionedUrl) -> Option<&OntologyVertex> {
+        self.data_types.get(url)
+    }
+
+    #[must_use]
+    pub fn property_type(&self, url: &VersionedUrl) -> Option<&OntologyVertex> {
+        self.property_types.get(url)
+    }
+
+    #[must_use]
+    pub fn entity_type(&self, url: &VersionedUrl) -> Option<&OntologyVertex> {
+        self.entity_types.get(url)
+    }
+
+    pub fn data_types(&self) -> impl Iterator<Item = (&VersionedUrl, &OntologyVertex)> {
+        self.data_types.iter()
+    }
+
+    pub fn property_types(&self) -> impl Iterator<Item = (&VersionedUrl, &OntologyVertex)> {
+        self.property_types.iter()
+    }
+
+    pub fn entity_types(&self) -> impl Iterator<Item = (&VersionedUrl, &OntologyVertex)> {
+        self.entity_types.iter()
+    }
+
+    /// Every data type, property type and entity type in the subgraph.
+    pub fn ontology(&self) -> impl Iterator<Item = (OntologyKind, &VersionedUrl, &OntologyVertex)> {
+        let data_types = self
+            .data_types
+            .iter()
+            .map(|(url, vertex)| (OntologyKind::DataType, url, vertex));
+        let property_types = self
+            .property_types
+            .iter()
+            .map(|(url, vertex)| (OntologyKind::PropertyType, url, vertex));
+        let entity_types = self
+            .entity_types
+            .iter()
+            .map(|(url, vertex)| (OntologyKind::EntityType, url, vertex));
+
+        data_types.chain(property_types).chain(entity_types)
+    }
+
+    #[must_use]
+    pub fn entity(&self, id: EntityId) -> Option<&EntityVertex> {
+        self.entities.get(&id)
+    }
+
+    pub fn entity_mut(&mut self, id: EntityId) -> Option<&mut EntityVertex> {
+        self.entities.get_mut(&id)
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = &EntityVertex> {
+        self.entities.values()
+    }
+
+    pub fn entities_mut(&mut self) -> impl Iterator<Item = &mut EntityVertex> {
+        self.entities.values_mut()
+    }
+
+    /// Consumes the subgraph and returns the latest revision of every entity.
+    #[must_use]
+    pub fn into_entities(self) -> Vec<Entity> {
+        self.entities
+            .into_values()
+            .map(EntityVertex::into_latest)
+            .collect()
+    }
+
+    /// All edges, which start at `vertex`.
+    #[must_use]
+    pub fn edges(&self, vertex: &VertexId) -> &[Edge] {
+        self.edges.get(vertex).map_or(&[], Vec::as_slice)
+    }
+
+    /// Endpoints of all edges of the given `kind`, which start at `vertex`.
+    ///
+    /// If `reversed` is `true`, the edges are followed in the opposite direction, e.g. following
+    /// [`EdgeKind::IsOfType`] reversed from an entity type yields all entities of that type.
+    pub fn neighbours<'a>(
+        &'a self,
+        vertex: &VertexId,
+        kind: EdgeKind,
+        reversed: bool,
+    ) -> impl Iterator<Item = &'a EdgeEndpoint> + 'a {
+        self.edges(vertex)
+            .iter()
+            .filter(move |edge| edge.kind == kind && edge.reversed == reversed)
+            .map(|edge| &edge.right_endpoint)
+    }
+
+    /// The entity types of the latest revision of the entity, which are part of this subgraph.
+    pub fn is_of_type(&self, entity: EntityId) -> impl Iterator<Item = &OntologyVertex> {
+        self.entity(entity)
+            .into_iter()
+            .flat_map(EntityVertex::entity_type_ids)
+            .filter_map(|entity_type| self.entity_type(entity_type))
+    }
+
+    /// The direct parents of the entity type, as recorded through [`EdgeKind::InheritsFrom`].
+    pub fn inherits_from<'a>(
+        &'a self,
+        entity_type: &VersionedUrl,
+    ) -> impl Iterator<Item = &'a VersionedUrl> + 'a {
+        self.neighbours(
+            &VertexId::Ontology(entity_type.clone()),
+            EdgeKind::InheritsFrom,
+            false,
+        )
+        .filter_map(EdgeEndpoint::ontology)
+    }
+
+    /// All ancestors of the entity type, which are reachable in this subgraph, each ancestor is
+    /// only returned once.
+    #[must_use]
+    pub fn ancestors(&self, entity_type: &VersionedUrl) -> Vec<&VersionedUrl> {
+        let mut visited = HashSet::new();
+        let mut ancestors = Vec::new();
+        let mut stack: Vec<_> = self.inherits_from(entity_type).collect();
+
+        while let Some(url) = stack.pop() {
+            if !visited.insert(url) {
+                continue;
+            }
+
+            ancestors.push(url);
+            stack.extend(self.inherits_from(url));
+        }
+
+        ancestors
+    }
+
+    /// The left entity of the latest revision of the link entity.
+    #[must_use]
+    pub fn left_entity(&self, link: EntityId) -> Option<&EntityVertex> {
+        let link_data = self.entity(link)?.latest().link_data.as_ref()?;
+
+        self.entity(link_data.left_entity_id)
+    }
+
+    /// The right entity of the latest revision of the link entity.
+    #[must_use]
+    pub fn right_entity(&self, link: EntityId) -> Option<&EntityVertex> {
+        let link_data = self.entity(link)?.latest().link_data.as_ref()?;
+
+        self.entity(link_data.right_entity_id)
+    }
+
+    /// Entities, which are the target of the outgoing edges of `kind` of the specific revision of
+    /// the entity.
+    pub fn entity_neighbours(
+        &self,
+        entity: EntityId,
+        revision: RevisionId,
+        kind: EdgeKind,
+        reversed: bool,
+    ) -> impl Iterator<Item = &EntityVertex> + '_ {
+        let vertex = VertexId::Entity {
+            entity_id: entity,
+            revision_id: revision,
+        };
+
+        self.neighbours(&vertex, kind, reversed)
+            .filter_map(EdgeEndpoint::entity_id)
+            .filter_map(|entity_id| self.entity(entity_id))
+    }
+}
+
+impl LinkSource for Subgraph {
+    fn outgoing_links(&self, entity: EntityId) -> Vec<Linked<&Entity, Option<&Entity>>> {
+        self.entities()
+            .map(EntityVertex::latest)
+            .filter(|link| {
+                link.link_data
+                    .as_ref()
+                    .is_some_and(|link_data| link_data.left_entity_id == entity)
+            })
+            .map(|link| {
+                let right = link.link_data.as_ref().and_then(|link_data| {
+                    self.entity(link_data.right_entity_id)
+                        .map(EntityVertex::latest)
+                });
+
+                Linked { link, right }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::fixture;
+
+    const ALICE: &str = fixture::ENTITY_ID;
+    const BOB: &str = "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e~1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c5d";
+    const KNOWS: &str = "e9f6a3c2-31a4-4d29-9a9e-0d0e1b2c3d4e~9f8e7d6c-5b4a-4c3d-8e2f-1a0b9c8d7e6f";
+
+    const REVISION: &str = fixture::TIMESTAMP;
+
+    fn entity_type(title: &str) -> Value {
+        json!({
+            "kind": "entityType",
+            "inner": {
+                "schema": { "title": title },
+                "metadata": {}
+            }
+        })
+    }
+
+    fn subgraph() -> Subgraph {
+        let axes = json!({
+            "pinned": { "axis": "transactionTime", "timestamp": null },
+            "variable": {
+                "axis": "decisionTime",
+                "interval": { "start": null, "end": null }
+            }
+        });
+
+        serde_json::from_value(json!({
+            "roots": [
+                { "baseId": "https://example.com/entity-type/person/", "revisionId": 1 },
+                { "baseId": ALICE, "revisionId": REVISION }
+            ],
+            "vertices": {
+                "https://example.com/entity-type/person/": {
+                    "1": entity_type("Person")
+                },
+                "https://example.com/entity-type/agent/": {
+                    "1": entity_type("Agent")
+                },
+                "https://example.com/entity-type/thing/": {
+                    "1": entity_type("Thing")
+                },
+                "https://example.com/entity-type/knows/": {
+                    "1": entity_type("Knows")
+                },
+                ALICE: {
+                    REVISION: { "kind": "entity", "inner": fixture::entity().into_value() }
+                },
+                BOB: {
+                    REVISION: {
+                        "kind": "entity",
+                        "inner": fixture::entity().entity_id(BOB).into_value()
+                    }
+                },
+                KNOWS: {
+                    REVISION: {
+                        "kind": "entity",
+                        "inner": fixture::entity()
+                            .entity_id(KNOWS)
+                            .entity_type("https://example.com/entity-type/knows/v/1")
+                            .link_data(json!({ "leftEntityId": ALICE, "rightEntityId": BOB }))
+                            .into_value()
+                    }
+                }
+            },
+            "edges": {
+                "https://example.com/entity-type/person/": {
+                    "1": [{
+                        "kind": "INHERITS_FROM",
+                        "reversed": false,
+                        "rightEndpoint": {
+                            "baseId": "https://example.com/entity-type/agent/",
+                            "revisionId": 1
+                        }
+                    }]
+                },
+                "https://example.com/entity-type/agent/": {
+                    "1": [{
+                        "kind": "INHERITS_FROM",
+                        "reversed": false,
+                        "rightEndpoint": {
+                            "baseId": "https://example.com/entity-type/thing/",
+                            "revisionId": 1
+                        }
+                    }]
+                },
+                KNOWS: {
+                    REVISION: [{
+                        "kind": "HAS_LEFT_ENTITY",
+                        "reversed": false,
+                        "rightEndpoint": { "entityId": ALICE, "interval": fixture::since(REVISION) }
+                    }, {
+                        "kind": "HAS_RIGHT_ENTITY",
+                        "reversed": false,
+                        "rightEndpoint": { "entityId": BOB, "interval": fixture::since(REVISION) }
+                    }, {
+                        "kind": "IS_OF_TYPE",
+                        "reversed": false,
+                        "rightEndpoint": {
+                            "baseId": "https://example.com/entity-type/knows/",
+                            "revisionId": 1
+                        }
+                    }]
+                }
+            },
+            "depths": {},
+            "temporalAxes": { "initial": axes, "resolved": axes }
+        }))
+        .expect("should be a valid subgraph")
+    }
+
+    fn entity_id(id: &str) -> EntityId {
+        serde_json::from_value(Value::String(id.into())).expect("should be a valid entity id")
+    }
+
+    fn url(url: &str) -> VersionedUrl {
+        serde_json::from_value(Value::String(url.into())).expect("should be a valid URL")
+    }
+
+    #[test]
+    fn vertices() {
+        let subgraph = subgraph();
+
+        assert_eq!(subgraph.roots(), [
+            VertexId::Ontology(url("https://example.com/entity-type/person/v/1")),
+            VertexId::Entity {
+                entity_id: entity_id(ALICE),
+                revision_id: subgraph
+                    .entity(entity_id(ALICE))
+                    .expect("should contain entity")
+                    .latest_version(),
+            }
+        ]);
+
+        assert_eq!(subgraph.entity_types().count(), 4);
+        assert_eq!(subgraph.ontology().count(), 4);
+        assert_eq!(subgraph.entities().count(), 3);
+
+        let person = subgraph
+            .is_of_type(entity_id(ALICE))
+            .next()
+            .expect("should have entity type");
+        assert_eq!(person.schema["title"], "Person");
+    }
+
+    #[test]
+    fn ancestors() {
+        let subgraph = subgraph();
+        let person = url("https://example.com/entity-type/person/v/1");
+
+        let parents: Vec<_> = subgraph.inherits_from(&person).collect();
+        assert_eq!(parents, [&url("https://example.com/entity-type/agent/v/1")]);
+
+        let ancestors = subgraph.ancestors(&person);
+        assert_eq!(ancestors, [
+            &url("https://example.com/entity-type/agent/v/1"),
+            &url("https://example.com/entity-type/thing/v/1")
+        ]);
+    }
+
+    #[test]
+    fn links() {
+        let subgraph = subgraph();
+        let revision = subgraph
+            .entity(entity_id(KNOWS))
+            .expect("should contain link")
+            .latest_version();
+
+        let left = subgraph
+            .left_entity(entity_id(KNOWS))
+            .expect("should have left entity");
+        assert_eq!(left.entity_id(), entity_id(ALICE));
+
+        let right: Vec<_> = subgraph
+            .entity_neighbours(entity_id(KNOWS), revision, EdgeKind::HasRightEntity, false)
+            .map(EntityVertex::entity_id)
+            .collect();
+        assert_eq!(right, [entity_id(BOB)]);
+
+        let outgoing = subgraph.outgoing_links(entity_id(ALICE));
+        assert_eq!(outgoing.len(), 1);
+        assert_eq!(
+            outgoing[0]
+                .right
+                .map(|entity| entity.metadata.record_id.entity_id),
+            Some(entity_id(BOB))
+        );
+        assert!(subgraph.outgoing_links(entity_id(BOB)).is_empty());
+    }
+}
@@ -0,0 +1,124 @@
+use alloc::string::String;
+
+use serde::{Deserialize, Deserializer};
+use type_system::url::VersionedUrl;
+
+use crate::{
+    entity::{EntityId, LeftClosedTemporalInterval},
+    subgraph::{versioned_url, SubgraphError},
+};
+
+/// Kind of an edge, the graph sends these in `SCREAMING_SNAKE_CASE`, while resolve depths use
+/// `camelCase`, both are accepted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub enum EdgeKind {
+    #[serde(rename = "INHERITS_FROM", alias = "inheritsFrom")]
+    InheritsFrom,
+    #[serde(rename = "CONSTRAINS_VALUES_ON", alias = "constrainsValuesOn")]
+    ConstrainsValuesOn,
+    #[serde(rename = "CONSTRAINS_PROPERTIES_ON", alias = "constrainsPropertiesOn")]
+    ConstrainsPropertiesOn,
+    #[serde(rename = "CONSTRAINS_LINKS_ON", alias = "constrainsLinksOn")]
+    ConstrainsLinksOn,
+    #[serde(
+        rename = "CONSTRAINS_LINK_DESTINATIONS_ON",
+        alias = "constrainsLinkDestinationsOn"
+    )]
+    ConstrainsLinkDestinationsOn,
+    #[serde(rename = "IS_OF_TYPE", alias = "isOfType")]
+    IsOfType,
+    #[serde(rename = "HAS_LEFT_ENTITY", alias = "hasLeftEntity")]
+    HasLeftEntity,
+    #[serde(rename = "HAS_RIGHT_ENTITY", alias = "hasRightEntity")]
+    HasRightEntity,
+}
+
+/// Target of an edge.
+///
+/// Edges to entities do not point to a specific revision, but to every revision of the entity,
+/// which is valid during `interval`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EdgeEndpoint {
+    Ontology(VersionedUrl),
+    Entity {
+        entity_id: EntityId,
+        interval: LeftClosedTemporalInterval,
+    },
+}
+
+impl EdgeEndpoint {
+    #[must_use]
+    pub const fn ontology(&self) -> Option<&VersionedUrl> {
+        match self {
+            Self::Ontology(url) => Some(url),
+            Self::Entity { .. } => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn entity_id(&self) -> Option<EntityId> {
+        match self {
+            Self::Ontology(_) => None,
+            Self::Entity { entity_id, .. } => Some(*entity_id),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawEdgeEndpoint {
+    Entity {
+        #[serde(rename = "entityId")]
+        entity_id: EntityId,
+        interval: LeftClosedTemporalInterval,
+    },
+    Ontology {
+        #[serde(rename = "baseId")]
+        base_id: String,
+        #[serde(rename = "revisionId")]
+        revision_id: u32,
+    },
+}
+
+impl TryFrom<RawEdgeEndpoint> for EdgeEndpoint {
+    type Error = SubgraphError;
+
+    fn try_from(value: RawEdgeEndpoint) -> Result<Self, Self::Error> {
+        match value {
+            RawEdgeEndpoint::Entity {
+                entity_id,
+                interval,
+            } => Ok(Self::Entity {
+                entity_id,
+                interval,
+            }),
+            RawEdgeEndpoint::Ontology {
+                base_id,
+                revision_id,
+            } => versioned_url(base_id, revision_id).map(Self::Ontology),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for EdgeEndpoint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        RawEdgeEndpoint::deserialize(deserializer)?
+            .try_into()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Outgoing edge of a vertex.
+///
+/// If `reversed` is `true`, the edge has been traversed in the opposite direction, e.g. an
+/// `IS_OF_TYPE` edge from an entity type to an entity.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Edge {
+    pub kind: EdgeKind,
+    pub reversed: bool,
+    pub right_endpoint: EdgeEndpoint,
+}
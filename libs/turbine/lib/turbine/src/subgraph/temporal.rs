@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+use crate::entity::{Interval, Timestamp};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimeAxis {
+    DecisionTime,
+    TransactionTime,
+}
+
+/// Bound of an interval used in a query, unlike the bounds of an entity edition, both sides of
+/// the interval can be inclusive, exclusive or unbounded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "limit")]
+pub enum TemporalBound {
+    Unbounded,
+    Inclusive(Timestamp),
+    Exclusive(Timestamp),
+}
+
+/// Time axis, which is pinned to a single point in time.
+///
+/// If `timestamp` is `None` the graph will use the current time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedTemporalAxis {
+    pub axis: TimeAxis,
+    pub timestamp: Option<Timestamp>,
+}
+
+/// Time axis, which spans over an interval.
+///
+/// A bound of `None` will be resolved by the graph, the start defaults to unbounded, while the
+/// end defaults to the current time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableTemporalAxis {
+    pub axis: TimeAxis,
+    pub interval: Interval<Option<TemporalBound>, Option<TemporalBound>>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryTemporalAxes {
+    pub pinned: PinnedTemporalAxis,
+    pub variable: VariableTemporalAxis,
+}
+
+impl QueryTemporalAxes {
+    /// Query every decision time, as currently known to the graph.
+    #[must_use]
+    pub const fn current() -> Self {
+        Self {
+            pinned: PinnedTemporalAxis {
+                axis: TimeAxis::TransactionTime,
+                timestamp: None,
+            },
+            variable: VariableTemporalAxis {
+                axis: TimeAxis::DecisionTime,
+                interval: Interval {
+                    start: None,
+                    end: None,
+                },
+            },
+        }
+    }
+}
+
+/// Temporal axes of a subgraph, `initial` are the axes as sent in the query, while `resolved` are
+/// the axes after the graph has filled in all missing bounds.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubgraphTemporalAxes {
+    pub initial: QueryTemporalAxes,
+    pub resolved: QueryTemporalAxes,
+}
@@ -0,0 +1,111 @@
+use alloc::{borrow::ToOwned, string::String};
+
+use serde::{de::value::StrDeserializer, Deserialize, Serialize};
+use serde_json::Value;
+use time::{format_description::well_known::Iso8601, OffsetDateTime};
+use type_system::url::VersionedUrl;
+
+use crate::{
+    entity::{EntityId, RevisionId},
+    subgraph::{versioned_url, SubgraphError},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OntologyKind {
+    DataType,
+    PropertyType,
+    EntityType,
+}
+
+/// Data type, property type or entity type as returned by the graph.
+///
+/// The schema is kept in its JSON representation, as the type-system crate used during code
+/// generation is not necessarily the same as the one used at runtime.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OntologyVertex {
+    pub schema: Value,
+    pub metadata: Value,
+}
+
+/// Identifier of a single vertex in a subgraph.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VertexId {
+    Ontology(VersionedUrl),
+    Entity {
+        entity_id: EntityId,
+        revision_id: RevisionId,
+    },
+}
+
+impl VertexId {
+    /// Parse the vertex identifier from the keys used in the `vertices` and `edges` maps of a
+    /// subgraph.
+    pub(crate) fn parse(base_id: &str, revision_id: &str) -> Result<Self, SubgraphError> {
+        if let Ok(entity_id) =
+            EntityId::deserialize(StrDeserializer::<serde::de::value::Error>::new(base_id))
+        {
+            let time = OffsetDateTime::parse(revision_id, &Iso8601::DEFAULT)
+                .map_err(|_error| SubgraphError::InvalidRevisionId)?;
+
+            return Ok(Self::Entity {
+                entity_id,
+                revision_id: RevisionId::from(time),
+            });
+        }
+
+        let version = revision_id
+            .parse()
+            .map_err(|_error| SubgraphError::InvalidRevisionId)?;
+
+        versioned_url(base_id.to_owned(), version).map(Self::Ontology)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawVertexId {
+    Entity {
+        #[serde(rename = "baseId")]
+        base_id: EntityId,
+        #[serde(rename = "revisionId")]
+        revision_id: RevisionId,
+    },
+    Ontology {
+        #[serde(rename = "baseId")]
+        base_id: String,
+        #[serde(rename = "revisionId")]
+        revision_id: u32,
+    },
+}
+
+impl TryFrom<RawVertexId> for VertexId {
+    type Error = SubgraphError;
+
+    fn try_from(value: RawVertexId) -> Result<Self, Self::Error> {
+        match value {
+            RawVertexId::Entity {
+                base_id,
+                revision_id,
+            } => Ok(Self::Entity {
+                entity_id: base_id,
+                revision_id,
+            }),
+            RawVertexId::Ontology {
+                base_id,
+                revision_id,
+            } => versioned_url(base_id, revision_id).map(Self::Ontology),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VertexId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        RawVertexId::deserialize(deserializer)?
+            .try_into()
+            .map_err(serde::de::Error::custom)
+    }
+}
@@ -1,23 +1,123 @@
+//! Builtin data types.
+//!
+//! [`Integer`], [`Date`], [`Time`], [`DateTime`], [`Uri`] and [`Email`] are not published data
+//! types, but [`Refinement`]s of [`Number`] and [`Text`], which share the URL of the data type they
+//! refine. Codegen uses them as the representation of data types of `type` integer, or with the
+//! respective `format`, which carry their own URL.
+
 mod boolean;
+pub mod constraint;
+mod date;
+mod date_time;
+mod email;
 mod empty_list;
+mod format;
+mod integer;
+mod native;
 mod null;
 mod number;
 mod object;
 mod text;
+mod time_of_day;
+mod uri;
 
 pub use boolean::Boolean;
+pub use date::Date;
+pub use date_time::DateTime;
+pub use email::Email;
 pub use empty_list::EmptyList;
+pub use integer::Integer;
 pub use null::Null;
 pub use number::Number;
 pub use object::Object;
 pub use text::Text;
+pub use time_of_day::Time;
+pub use uri::Uri;
+
+use crate::{
+    path::{Path, Segments, TypePath},
+    TypeUrl,
+};
+
+/// Rust-side refinement of a published data type, which is parsed into a native representation.
+///
+/// A refinement is identified by the URL of [`Self::Parent`], which it shares, together with
+/// [`Self::CONSTRAINT`], it does not have a URL of its own.
+pub trait Refinement: TypeUrl {
+    type Parent: TypeUrl;
 
-use crate::path::{Path, TypePath};
+    /// The JSON schema keyword and value enforced in addition to the parent, e.g.
+    /// `("format", "date")`.
+    const CONSTRAINT: (&'static str, &'static str);
+}
 
-pub struct DataTypePath;
+/// Path to a data type, data types are leaves and cannot be traversed any further.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DataTypePath(Segments);
 
 impl TypePath for DataTypePath {
+    fn from_segments(segments: Segments) -> Self {
+        Self(segments)
+    }
+
     fn path(self) -> Path<'static> {
-        Path::new(&[])
+        self.0.into_path()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+
+    use serde_json::value::RawValue;
+
+    use super::*;
+    use crate::{DataTypeRef, Type};
+
+    fn key<T: Refinement>() -> (String, (&'static str, &'static str)) {
+        (T::ID.to_string(), T::CONSTRAINT)
+    }
+
+    #[test]
+    fn refinements_share_the_parent_url() {
+        assert_eq!(Integer::ID, Number::ID);
+        assert_eq!(DateTime::ID, Text::ID);
+        assert_eq!(
+            Text::ID.to_string(),
+            "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1"
+        );
+
+        assert_ne!(key::<Date>(), key::<Time>());
+        assert_eq!(key::<Email>().1, ("format", "email"));
+        assert_eq!(key::<Integer>().1, ("type", "integer"));
+    }
+
+    #[test]
+    fn raw_strings_with_escape_sequences() {
+        let text: &RawValue = serde_json::from_str(r#""line\nbreak \"quoted\" \u00e9""#)
+            .expect("should be valid JSON");
+        let text = <Text as Type>::Ref::try_from_raw(text).expect("should be text");
+        assert_eq!(text.as_str(), "line\nbreak \"quoted\" \u{e9}");
+
+        let uri: &RawValue =
+            serde_json::from_str(r#""https:\/\/example.com\/path""#).expect("should be valid JSON");
+        let uri = <Uri as Type>::Ref::try_from_raw(uri).expect("should be a URI");
+        assert_eq!(uri.as_str(), "https://example.com/path");
+
+        let email: &RawValue =
+            serde_json::from_str(r#""alice\u0040example.com""#).expect("should be valid JSON");
+        let email = <Email as Type>::Ref::try_from_raw(email).expect("should be an email address");
+        assert_eq!(email.as_str(), "alice@example.com");
+    }
+
+    #[test]
+    fn raw_numbers() {
+        let number: &RawValue = serde_json::from_str("1.5").expect("should be valid JSON");
+        let number = <Number as Type>::Ref::try_from_raw(number).expect("should be a number");
+        assert_eq!(number.as_f64(), Some(1.5));
+        assert_eq!(
+            number,
+            Number::from(serde_json::Number::from_f64(1.5).expect("should be finite")).as_ref()
+        );
     }
 }
@@ -3,11 +3,11 @@ use core::ops::{Deref, DerefMut};
 use error_stack::{Report, Result};
 use onlyerror::Error;
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{value::RawValue, Value};
 
 use crate::{
-    types::data::DataTypePath, url, DataType, DataTypeMut, DataTypeRef, Type, TypeMut, TypeRef,
-    TypeTraverse, TypeUrl, VersionedUrlRef,
+    raw::to_value_lossy, types::data::DataTypePath, url, DataType, DataTypeMut, DataTypeRef, Type,
+    TypeMut, TypeRef, TypeTraverse, TypeUrl, VersionedUrlRef,
 };
 
 #[derive(Debug, Clone, Error)]
@@ -50,8 +50,6 @@ impl DerefMut for Boolean {
 }
 
 impl TypeUrl for Boolean {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
         url!("https://blockprotocol.org/@blockprotocol/types/data-type/boolean/" / v / 1);
 }
@@ -106,6 +104,11 @@ impl<'a> DataTypeRef<'a> for Boolean {
             |value| Ok(Self(value)),
         )
     }
+
+    fn try_from_raw(value: &'a RawValue) -> Result<Self, Self::Error> {
+        // scalar values are cheap to parse and not borrowed
+        <Self as DataTypeRef<'_>>::try_from_value(&to_value_lossy(value))
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -126,8 +129,6 @@ impl DerefMut for BooleanMut<'_> {
 }
 
 impl TypeUrl for BooleanMut<'_> {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
         url!("https://blockprotocol.org/@blockprotocol/types/data-type/boolean/" / v / 1);
 }
@@ -0,0 +1,427 @@
+//! Helpers used by generated data types to validate the constraints of their schema.
+//!
+//! Every function is intentionally lenient for constraints it does not understand, an unknown
+//! `format` is always satisfied, as the graph is the source of truth for validation.
+
+use alloc::{borrow::ToOwned, boxed::Box, format, string::String};
+use core::{cmp::Ordering, fmt};
+
+use error_stack::{Report, Result};
+use once_cell::race::OnceBox;
+use onlyerror::Error;
+pub use regex_automata::meta::Regex;
+use serde_json::{Number, Value};
+use time::{format_description::well_known::Rfc3339, Date, OffsetDateTime, Time, UtcOffset};
+
+/// Relative tolerance used when checking `multipleOf` on floating point numbers.
+const EPSILON: f64 = 1e-9;
+
+/// `2^53`, every floating point number of at least this magnitude is an integer.
+const MAX_SAFE_FLOAT: f64 = 9_007_199_254_740_992.0;
+
+/// `2^64`, every integer representable by [`Number`] is strictly smaller in magnitude.
+const MAX_NUMBER_INTEGER: f64 = 1.844_674_407_370_955_2e19;
+
+/// Returns `true` if `value` adheres to the JSON schema `format`.
+///
+/// Supported formats are `date-time`, `date`, `time`, `email` and `uri`, every other format is
+/// accepted.
+#[must_use]
+pub fn matches_format(format: &str, value: &str) -> bool {
+    match format {
+        "date-time" => parse_date_time(value).is_some(),
+        "date" => parse_date(value).is_some(),
+        "time" => parse_time(value).is_some(),
+        "email" => is_email(value),
+        "uri" => is_uri(value),
+        _ => true,
+    }
+}
+
+/// Parse an RFC 3339 `date-time`.
+pub(crate) fn parse_date_time(value: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(value, &Rfc3339).ok()
+}
+
+/// Parse an RFC 3339 `full-date`, by parsing it as the midnight of that day.
+pub(crate) fn parse_date(value: &str) -> Option<Date> {
+    if value.contains(['T', 't', ' ']) {
+        return None;
+    }
+
+    parse_date_time(&format!("{value}T00:00:00Z")).map(OffsetDateTime::date)
+}
+
+/// Parse an RFC 3339 `full-time`, by parsing it as the time of the unix epoch.
+pub(crate) fn parse_time(value: &str) -> Option<(Time, UtcOffset)> {
+    if value.contains(['T', 't']) {
+        return None;
+    }
+
+    parse_date_time(&format!("1970-01-01T{value}"))
+        .map(|date_time| (date_time.time(), date_time.offset()))
+}
+
+pub(crate) fn format_date_time(value: OffsetDateTime) -> Option<String> {
+    value.format(&Rfc3339).ok()
+}
+
+pub(crate) fn format_date(value: Date) -> Option<String> {
+    let mut value = format_date_time(value.midnight().assume_utc())?;
+    value.truncate(value.find('T')?);
+
+    Some(value)
+}
+
+pub(crate) fn format_time(time: Time, offset: UtcOffset) -> Option<String> {
+    let value = format_date_time(
+        OffsetDateTime::UNIX_EPOCH
+            .replace_time(time)
+            .replace_offset(offset),
+    )?;
+
+    value.split_once('T').map(|(_, time)| time.to_owned())
+}
+
+pub(crate) fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.rsplit_once('@') else {
+        return false;
+    };
+
+    !local.is_empty()
+        && !domain.is_empty()
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !value.contains(char::is_whitespace)
+}
+
+pub(crate) fn is_uri(value: &str) -> bool {
+    let Some((scheme, _)) = value.split_once(':') else {
+        return false;
+    };
+
+    let mut chars = scheme.chars();
+
+    chars.next().is_some_and(|char| char.is_ascii_alphabetic())
+        && chars.all(|char| char.is_ascii_alphanumeric() || matches!(char, '+' | '-' | '.'))
+        && !value.contains(char::is_whitespace)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Error)]
+#[error("invalid regular expression")]
+pub struct PatternError;
+
+/// Compile the regular expression of a `pattern` constraint.
+///
+/// # Errors
+///
+/// [`PatternError`], if `pattern` is not a valid regular expression
+pub fn compile_pattern(pattern: &str) -> Result<Regex, PatternError> {
+    Regex::new(pattern).map_err(|error| Report::new(PatternError).attach_printable(error))
+}
+
+/// Regular expression of a `pattern` constraint, which is compiled once on first use.
+///
+/// Generated data types store every pattern in a `static`, the expression has been validated
+/// during code generation.
+pub struct Pattern {
+    source: &'static str,
+    regex: OnceBox<Regex>,
+}
+
+impl Pattern {
+    #[must_use]
+    pub const fn new(source: &'static str) -> Self {
+        Self {
+            source,
+            regex: OnceBox::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        self.source
+    }
+
+    /// # Panics
+    ///
+    /// if the expression is invalid, which is rejected by codegen before a `Pattern` is emitted
+    pub fn regex(&self) -> &Regex {
+        self.regex.get_or_init(|| {
+            Box::new(compile_pattern(self.source).expect("pattern should be validated by codegen"))
+        })
+    }
+}
+
+impl fmt::Debug for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Pattern").field(&self.source).finish()
+    }
+}
+
+/// Returns `true` if `regex` matches anywhere in `value`, as mandated by JSON schema.
+#[must_use]
+pub fn matches_pattern(regex: &Regex, value: &str) -> bool {
+    regex.is_match(value)
+}
+
+/// Numeric limit of a constraint (`minimum`, `multipleOf`, ...), integers are kept exact.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Limit {
+    Integer(i128),
+    Float(f64),
+}
+
+impl Limit {
+    #[must_use]
+    pub fn from_number(number: &Number) -> Option<Self> {
+        let integer = number
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| number.as_u64().map(i128::from));
+
+        integer
+            .map(Self::Integer)
+            .or_else(|| number.as_f64().map(Self::Float))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    const fn as_f64(self) -> f64 {
+        match self {
+            Self::Integer(value) => value as f64,
+            Self::Float(value) => value,
+        }
+    }
+}
+
+/// Compare an integer with a float without losing precision on either side.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn compare_integer_float(integer: i128, float: f64) -> Option<Ordering> {
+    if float.is_nan() {
+        return None;
+    }
+
+    if float >= MAX_NUMBER_INTEGER {
+        return Some(Ordering::Less);
+    }
+
+    if float < -MAX_NUMBER_INTEGER {
+        return Some(Ordering::Greater);
+    }
+
+    // `float` is within the range of `i128`, truncation towards zero is exact and the truncated
+    // value is representable as float again
+    let truncated = float as i128;
+
+    match integer.cmp(&truncated) {
+        Ordering::Equal => (truncated as f64).partial_cmp(&float),
+        ordering => Some(ordering),
+    }
+}
+
+/// Compare `value` to `limit`, integers are compared exactly, instead of converting them to
+/// floating point numbers first.
+///
+/// Returns `None` if the numbers are not comparable.
+#[must_use]
+pub fn compare(value: &Number, limit: Limit) -> Option<Ordering> {
+    match (Limit::from_number(value)?, limit) {
+        (Limit::Integer(value), Limit::Integer(limit)) => Some(value.cmp(&limit)),
+        (Limit::Float(value), Limit::Float(limit)) => value.partial_cmp(&limit),
+        (Limit::Integer(value), Limit::Float(limit)) => compare_integer_float(value, limit),
+        (Limit::Float(value), Limit::Integer(limit)) => {
+            compare_integer_float(limit, value).map(Ordering::reverse)
+        }
+    }
+}
+
+/// Returns `true` if `value` is an integer, this includes floating point numbers without a
+/// fractional part, like `1.0`.
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub fn is_integer(value: &Value) -> bool {
+    let Value::Number(number) = value else {
+        return false;
+    };
+
+    if number.is_i64() || number.is_u64() {
+        return true;
+    }
+
+    let Some(value) = number.as_f64() else {
+        return false;
+    };
+
+    if !value.is_finite() {
+        return false;
+    }
+
+    if value >= MAX_SAFE_FLOAT || value <= -MAX_SAFE_FLOAT {
+        return true;
+    }
+
+    compare_integer_float(value as i128, value) == Some(Ordering::Equal)
+}
+
+/// Returns `true` if `value` is a multiple of `divisor`, `divisor` must be strictly positive.
+///
+/// Integers are checked exactly, floating point numbers with a relative tolerance.
+#[must_use]
+pub fn is_multiple_of(value: &Number, divisor: Limit) -> bool {
+    let Some(value) = Limit::from_number(value) else {
+        return false;
+    };
+
+    if let (Limit::Integer(value), Limit::Integer(divisor)) = (value, divisor) {
+        return divisor > 0 && value % divisor == 0;
+    }
+
+    let value = value.as_f64();
+    let divisor = divisor.as_f64();
+
+    if divisor <= 0.0 {
+        return false;
+    }
+
+    let remainder = value % divisor;
+    let remainder = if remainder < 0.0 {
+        -remainder
+    } else {
+        remainder
+    };
+
+    remainder <= EPSILON * divisor || divisor - remainder <= EPSILON * divisor
+}
+
+/// Returns `true` if `value` is equal to `expected`, used for `const` and `enum` values.
+///
+/// Unlike `==`, numbers are compared by their value, `1` and `1.0` are equal.
+#[must_use]
+pub fn equals(value: &Value, expected: &Value) -> bool {
+    match (value, expected) {
+        (Value::Number(value), Value::Number(expected)) => Limit::from_number(expected)
+            .and_then(|expected| compare(value, expected))
+            .is_some_and(Ordering::is_eq),
+        (Value::Array(value), Value::Array(expected)) => {
+            value.len() == expected.len()
+                && value
+                    .iter()
+                    .zip(expected)
+                    .all(|(value, expected)| equals(value, expected))
+        }
+        (Value::Object(value), Value::Object(expected)) => {
+            value.len() == expected.len()
+                && expected.iter().all(|(key, expected)| {
+                    value.get(key).is_some_and(|value| equals(value, expected))
+                })
+        }
+        (value, expected) => value == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn format() {
+        assert!(matches_format("date-time", "2023-05-01T12:00:00Z"));
+        assert!(!matches_format("date-time", "2023-05-01"));
+
+        assert!(matches_format("date", "2023-05-01"));
+        assert!(!matches_format("date", "2023-05-01T12:00:00Z"));
+
+        assert!(matches_format("time", "12:00:00+02:00"));
+        assert!(!matches_format("time", "25:00:00Z"));
+
+        assert!(matches_format("email", "alice@example.com"));
+        assert!(!matches_format("email", "alice"));
+
+        assert!(matches_format("uri", "https://example.com"));
+        assert!(!matches_format("uri", "example.com"));
+
+        assert!(matches_format("unknown", "anything"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let date = parse_date("2023-05-01").expect("should be a valid date");
+        assert_eq!(format_date(date).as_deref(), Some("2023-05-01"));
+
+        let (time, offset) = parse_time("12:30:00-05:00").expect("should be a valid time");
+        assert_eq!(format_time(time, offset).as_deref(), Some("12:30:00-05:00"));
+    }
+
+    #[test]
+    fn pattern() {
+        static PATTERN: Pattern = Pattern::new("^[a-z]+$");
+
+        assert!(matches_pattern(PATTERN.regex(), "abc"));
+        assert!(!matches_pattern(PATTERN.regex(), "ABC"));
+
+        let regex = compile_pattern("b").expect("should be a valid pattern");
+        assert!(matches_pattern(&regex, "abc"));
+
+        assert!(compile_pattern("(").is_err());
+    }
+
+    #[test]
+    fn integer() {
+        assert!(is_integer(&json!(1)));
+        assert!(is_integer(&json!(1.0)));
+        assert!(is_integer(&json!(-3.0)));
+        assert!(is_integer(&json!(u64::MAX)));
+        assert!(!is_integer(&json!(1.5)));
+        assert!(!is_integer(&json!("1")));
+    }
+
+    #[test]
+    fn bounds() {
+        let large = Number::from(u64::MAX);
+        // `u64::MAX` is rounded up to `2^64` as float
+        let float = Limit::Float(MAX_NUMBER_INTEGER);
+
+        assert_eq!(compare(&large, float), Some(Ordering::Less));
+        assert_eq!(
+            compare(&large, Limit::Integer(i128::from(u64::MAX))),
+            Some(Ordering::Equal)
+        );
+
+        let one = Number::from_f64(1.0).expect("should be finite");
+        assert_eq!(compare(&one, Limit::Integer(1)), Some(Ordering::Equal));
+        assert_eq!(
+            compare(&Number::from(1), Limit::Float(1.5)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare(&Number::from(-2), Limit::Float(-1.5)),
+            Some(Ordering::Less)
+        );
+    }
+
+    #[test]
+    fn multiple_of() {
+        let number = |value: f64| Number::from_f64(value).expect("should be finite");
+
+        assert!(is_multiple_of(&number(10.0), Limit::Float(2.5)));
+        assert!(is_multiple_of(&Number::from(-9), Limit::Integer(3)));
+        assert!(is_multiple_of(&number(0.3), Limit::Float(0.1)));
+        assert!(!is_multiple_of(&Number::from(10), Limit::Integer(3)));
+        assert!(!is_multiple_of(&Number::from(10), Limit::Integer(0)));
+        assert!(is_multiple_of(&Number::from(u64::MAX), Limit::Integer(5)));
+        assert!(!is_multiple_of(
+            &Number::from(u64::MAX - 1),
+            Limit::Integer(5)
+        ));
+    }
+
+    #[test]
+    fn json() {
+        assert!(equals(&json!({"a": 1}), &json!({"a": 1.0})));
+        assert!(!equals(&json!([1, 2]), &json!([2, 1])));
+        assert!(!equals(&json!({"a": 1}), &json!({"a": 1, "b": 2})));
+        assert!(equals(&json!(null), &json!(null)));
+    }
+}
@@ -0,0 +1,80 @@
+use core::ops::Deref;
+
+use error_stack::{Report, Result};
+use onlyerror::Error;
+use serde_json::Value;
+
+use crate::types::data::{
+    constraint::{format_date, parse_date},
+    native::{native_data_type, Native},
+};
+
+#[derive(Debug, Clone, Error)]
+pub enum DateError {
+    #[error("`{0:?}` is not an RFC 3339 full-date")]
+    NotADate(Value),
+    #[error("`{0}` cannot be represented as an RFC 3339 full-date")]
+    Unrepresentable(time::Date),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date(time::Date);
+
+impl Date {
+    #[must_use]
+    pub const fn new(value: time::Date) -> Self {
+        Self(value)
+    }
+
+    #[must_use]
+    pub const fn into_inner(self) -> time::Date {
+        self.0
+    }
+}
+
+impl From<time::Date> for Date {
+    fn from(value: time::Date) -> Self {
+        Self(value)
+    }
+}
+
+impl Deref for Date {
+    type Target = time::Date;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Native for Date {
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_str().and_then(parse_date).map(Self)
+    }
+
+    fn to_value(self) -> Option<Value> {
+        format_date(self.0).map(Value::String)
+    }
+}
+
+native_data_type!(
+    Date,
+    DateMut,
+    DateError::NotADate,
+    Text { "format": "date" }
+);
+
+impl DateMut<'_> {
+    /// # Errors
+    ///
+    /// if the value cannot be represented as an RFC 3339 full-date, e.g. the year is not between
+    /// 0 and 9999, the current value is left unchanged.
+    pub fn set(&mut self, value: impl Into<Date>) -> Result<(), DateError> {
+        let value = value.into();
+
+        if self.0.set(value) {
+            Ok(())
+        } else {
+            Err(Report::new(DateError::Unrepresentable(value.0)))
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use core::ops::Deref;
+
+use error_stack::{Report, Result};
+use onlyerror::Error;
+use serde_json::Value;
+use time::OffsetDateTime;
+
+use crate::types::data::{
+    constraint::{format_date_time, parse_date_time},
+    native::{native_data_type, Native},
+};
+
+#[derive(Debug, Clone, Error)]
+pub enum DateTimeError {
+    #[error("`{0:?}` is not an RFC 3339 date-time")]
+    NotADateTime(Value),
+    #[error("`{0}` cannot be represented as an RFC 3339 date-time")]
+    Unrepresentable(OffsetDateTime),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime(OffsetDateTime);
+
+impl DateTime {
+    #[must_use]
+    pub const fn new(value: OffsetDateTime) -> Self {
+        Self(value)
+    }
+
+    #[must_use]
+    pub const fn into_inner(self) -> OffsetDateTime {
+        self.0
+    }
+}
+
+impl From<OffsetDateTime> for DateTime {
+    fn from(value: OffsetDateTime) -> Self {
+        Self(value)
+    }
+}
+
+impl Deref for DateTime {
+    type Target = OffsetDateTime;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Native for DateTime {
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_str().and_then(parse_date_time).map(Self)
+    }
+
+    fn to_value(self) -> Option<Value> {
+        format_date_time(self.0).map(Value::String)
+    }
+}
+
+native_data_type!(
+    DateTime,
+    DateTimeMut,
+    DateTimeError::NotADateTime,
+    Text { "format": "date-time" }
+);
+
+impl DateTimeMut<'_> {
+    /// # Errors
+    ///
+    /// if the value cannot be represented as an RFC 3339 date-time, e.g. the year is not between
+    /// 0 and 9999, the current value is left unchanged.
+    pub fn set(&mut self, value: impl Into<DateTime>) -> Result<(), DateTimeError> {
+        let value = value.into();
+
+        if self.0.set(value) {
+            Ok(())
+        } else {
+            Err(Report::new(DateTimeError::Unrepresentable(value.0)))
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use onlyerror::Error;
+use serde_json::Value;
+
+use crate::types::data::{constraint::is_email, format::format_data_type};
+
+#[derive(Debug, Clone, Error)]
+pub enum EmailError {
+    #[error("`{0:?}` is not an email address")]
+    NotAnEmail(Value),
+}
+
+format_data_type!(
+    Email,
+    EmailRef,
+    EmailMut,
+    EmailError::NotAnEmail,
+    is_email,
+    "an email address",
+    "email"
+);
@@ -1,11 +1,11 @@
 use error_stack::{Report, Result};
 use onlyerror::Error;
 use serde::{ser::SerializeSeq, Serialize, Serializer};
-use serde_json::Value;
+use serde_json::{value::RawValue, Value};
 
 use crate::{
-    types::data::DataTypePath, url, DataType, DataTypeMut, DataTypeRef, Type, TypeMut, TypeRef,
-    TypeTraverse, TypeUrl, VersionedUrlRef,
+    raw::to_value_lossy, types::data::DataTypePath, url, DataType, DataTypeMut, DataTypeRef, Type,
+    TypeMut, TypeRef, TypeTraverse, TypeUrl, VersionedUrlRef,
 };
 
 #[derive(Debug, Clone, Error)]
@@ -30,8 +30,6 @@ impl Serialize for EmptyList {
 }
 
 impl TypeUrl for EmptyList {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
         url!("https://blockprotocol.org/@blockprotocol/types/data-type/emptyList/" / v / 1);
 }
@@ -101,6 +99,11 @@ impl<'a> DataTypeRef<'a> for EmptyList {
             },
         )
     }
+
+    fn try_from_raw(value: &'a RawValue) -> Result<Self, Self::Error> {
+        // the only valid value is `[]`, which is cheap to parse
+        <Self as DataTypeRef<'_>>::try_from_value(&to_value_lossy(value))
+    }
 }
 
 impl TypeMut for EmptyList {
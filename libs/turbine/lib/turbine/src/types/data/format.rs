@@ -0,0 +1,233 @@
+/// Generate a text data type `$name`, which only accepts strings satisfying `$check`, together
+/// with its `Ref` and `Mut` variants.
+///
+/// The mutable variant can only replace the value as a whole, to ensure it stays valid.
+/// `$error::$variant` is reported for every rejected value, and must take the offending value.
+/// The borrowed variant only owns its value if it has been read from raw JSON and contains escape
+/// sequences.
+///
+/// The type is a [`Refinement`] of [`Text`] by the JSON schema `format` and shares its URL.
+///
+/// [`Refinement`]: crate::types::data::Refinement
+/// [`Text`]: crate::types::data::Text
+macro_rules! format_data_type {
+    (
+        $name:ident,
+        $name_ref:ident,
+        $name_mut:ident,
+        $error:ident:: $variant:ident,
+        $check:path,
+        $description:literal,
+        $format:literal
+    ) => {
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+        pub struct $name(alloc::string::String);
+
+        impl $name {
+            #[doc = concat!("# Errors\n\nif the value is not ", $description)]
+            pub fn new(
+                value: impl Into<alloc::string::String>,
+            ) -> error_stack::Result<Self, $error> {
+                let value = value.into();
+
+                if $check(&value) {
+                    Ok(Self(value))
+                } else {
+                    Err(error_stack::Report::new($error::$variant(
+                        serde_json::Value::String(value),
+                    )))
+                }
+            }
+
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl core::ops::Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl $crate::TypeUrl for $name {
+            const ID: $crate::VersionedUrlRef<'static> =
+                <$crate::types::data::Text as $crate::TypeUrl>::ID;
+        }
+
+        impl $crate::types::data::Refinement for $name {
+            type Parent = $crate::types::data::Text;
+
+            const CONSTRAINT: (&'static str, &'static str) = ("format", $format);
+        }
+
+        impl $crate::TypeTraverse for $name {
+            type Path = $crate::types::data::DataTypePath;
+        }
+
+        impl $crate::Type for $name {
+            type Mut<'a> = $name_mut<'a> where Self: 'a;
+            type Ref<'a> = $name_ref<'a> where Self: 'a;
+
+            fn as_mut(&mut self) -> Self::Mut<'_> {
+                $name_mut(&mut self.0)
+            }
+
+            fn as_ref(&self) -> Self::Ref<'_> {
+                $name_ref(alloc::borrow::Cow::Borrowed(&self.0))
+            }
+        }
+
+        impl $crate::DataType for $name {
+            type Error = $error;
+
+            fn try_from_value(value: serde_json::Value) -> error_stack::Result<Self, Self::Error> {
+                match value {
+                    serde_json::Value::String(value) if $check(&value) => Ok(Self(value)),
+                    value => Err(error_stack::Report::new($error::$variant(value))),
+                }
+            }
+
+            fn is_valid_value(value: &serde_json::Value) -> bool {
+                value.as_str().is_some_and($check)
+            }
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+        pub struct $name_ref<'a>(alloc::borrow::Cow<'a, str>);
+
+        impl $name_ref<'_> {
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl core::ops::Deref for $name_ref<'_> {
+            type Target = str;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl $crate::TypeUrl for $name_ref<'_> {
+            const ID: $crate::VersionedUrlRef<'static> = <$name as $crate::TypeUrl>::ID;
+        }
+
+        impl $crate::TypeRef for $name_ref<'_> {
+            type Owned = $name;
+
+            fn into_owned(self) -> Self::Owned {
+                $name(self.0.into_owned())
+            }
+        }
+
+        impl $crate::TypeTraverse for $name_ref<'_> {
+            type Path = $crate::types::data::DataTypePath;
+        }
+
+        impl<'a> $crate::DataTypeRef<'a> for $name_ref<'a> {
+            type Error = $error;
+
+            fn try_from_value(value: &'a serde_json::Value) -> error_stack::Result<Self, Self::Error> {
+                match value {
+                    serde_json::Value::String(value) if $check(value.as_str()) => {
+                        Ok(Self(alloc::borrow::Cow::Borrowed(value)))
+                    }
+                    value => Err(error_stack::Report::new($error::$variant(value.clone()))),
+                }
+            }
+
+            fn try_from_raw(
+                value: &'a serde_json::value::RawValue,
+            ) -> error_stack::Result<Self, Self::Error> {
+                match $crate::raw::string(value) {
+                    Some(value) if $check(&value) => Ok(Self(value)),
+                    _ => Err(error_stack::Report::new($error::$variant(
+                        $crate::raw::to_value_lossy(value),
+                    ))),
+                }
+            }
+        }
+
+        #[doc = concat!(
+            "Mutable ", $description, ", the value can only be replaced as a whole, to ensure that ",
+            "it stays valid."
+        )]
+        #[derive(Debug, serde::Serialize)]
+        pub struct $name_mut<'a>(&'a mut alloc::string::String);
+
+        impl $name_mut<'_> {
+            #[doc = concat!(
+                "# Errors\n\nif the value is not ", $description,
+                ", the current value is left unchanged."
+            )]
+            pub fn set(
+                &mut self,
+                value: impl Into<alloc::string::String>,
+            ) -> error_stack::Result<(), $error> {
+                let value = value.into();
+
+                if $check(&value) {
+                    *self.0 = value;
+                    Ok(())
+                } else {
+                    Err(error_stack::Report::new($error::$variant(
+                        serde_json::Value::String(value),
+                    )))
+                }
+            }
+        }
+
+        impl core::ops::Deref for $name_mut<'_> {
+            type Target = str;
+
+            fn deref(&self) -> &Self::Target {
+                self.0.as_str()
+            }
+        }
+
+        impl $crate::TypeUrl for $name_mut<'_> {
+            const ID: $crate::VersionedUrlRef<'static> = <$name as $crate::TypeUrl>::ID;
+        }
+
+        impl $crate::TypeTraverse for $name_mut<'_> {
+            type Path = $crate::types::data::DataTypePath;
+        }
+
+        impl $crate::TypeMut for $name_mut<'_> {
+            type Owned = $name;
+
+            fn into_owned(self) -> Self::Owned {
+                $name(self.0.clone())
+            }
+        }
+
+        impl<'a> $crate::DataTypeMut<'a> for $name_mut<'a> {
+            type Error = $error;
+
+            fn try_from_value(
+                value: &'a mut serde_json::Value,
+            ) -> error_stack::Result<Self, Self::Error> {
+                match value {
+                    serde_json::Value::String(value) => {
+                        if $check(value) {
+                            Ok(Self(value))
+                        } else {
+                            Err(error_stack::Report::new($error::$variant(
+                                serde_json::Value::String(value.clone()),
+                            )))
+                        }
+                    }
+                    value => Err(error_stack::Report::new($error::$variant(value.clone()))),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use format_data_type;
@@ -0,0 +1,162 @@
+use onlyerror::Error;
+use serde_json::Value;
+
+use crate::types::data::{
+    constraint::is_integer,
+    native::{native_data_type, Native},
+};
+
+#[derive(Debug, Clone, Error)]
+pub enum IntegerError {
+    #[error("`{0:?}` is not an integer")]
+    NotAnInteger(Value),
+}
+
+/// Storage of an [`Integer`], non-negative values are always stored as `u64`, so that every value
+/// has exactly one representation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Repr {
+    Negative(i64),
+    NonNegative(u64),
+}
+
+/// Integer, which is either representable as an `i64` or an `u64`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Integer(Repr);
+
+impl Integer {
+    #[must_use]
+    pub fn to_i64(self) -> Option<i64> {
+        match self.0 {
+            Repr::Negative(value) => Some(value),
+            Repr::NonNegative(value) => i64::try_from(value).ok(),
+        }
+    }
+
+    #[must_use]
+    pub const fn to_u64(self) -> Option<u64> {
+        match self.0 {
+            Repr::Negative(_) => None,
+            Repr::NonNegative(value) => Some(value),
+        }
+    }
+}
+
+macro_rules! impl_from_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Integer {
+                fn from(value: $ty) -> Self {
+                    let value = i64::from(value);
+
+                    u64::try_from(value).map_or(Self(Repr::Negative(value)), |value| {
+                        Self(Repr::NonNegative(value))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Integer {
+                fn from(value: $ty) -> Self {
+                    Self(Repr::NonNegative(u64::from(value)))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_signed!(i8, i16, i32, i64);
+impl_from_unsigned!(u8, u16, u32, u64);
+
+/// `2^64`, the exclusive upper bound of `u64`.
+const U64_END: f64 = 1.844_674_407_370_955_2e19;
+/// `-2^63`, the inclusive lower bound of `i64`.
+const I64_START: f64 = -9.223_372_036_854_776e18;
+
+impl Native for Integer {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from_value(value: &Value) -> Option<Self> {
+        if let Some(value) = value.as_u64() {
+            return Some(Self::from(value));
+        }
+
+        if let Some(value) = value.as_i64() {
+            return Some(Self::from(value));
+        }
+
+        // integral floats, like `1.0`, are integers as well, as long as they are in range
+        if !is_integer(value) {
+            return None;
+        }
+
+        let value = value.as_f64()?;
+
+        if (0.0..U64_END).contains(&value) {
+            Some(Self::from(value as u64))
+        } else if (I64_START..0.0).contains(&value) {
+            Some(Self::from(value as i64))
+        } else {
+            None
+        }
+    }
+
+    fn to_value(self) -> Option<Value> {
+        let value = match self.0 {
+            Repr::Negative(value) => Value::from(value),
+            Repr::NonNegative(value) => Value::from(value),
+        };
+
+        Some(value)
+    }
+}
+
+native_data_type!(
+    Integer,
+    IntegerMut,
+    IntegerError::NotAnInteger,
+    Number { "type": "integer" }
+);
+
+impl IntegerMut<'_> {
+    pub fn set(&mut self, value: impl Into<Integer>) {
+        let value = value.into();
+
+        // every `Integer` is either an `i64` or `u64` and therefore valid JSON
+        let _ = self.0.set(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn representation() {
+        assert_eq!(Integer::from(1_i8), Integer::from(1_u64));
+        assert!(Integer::from(-1_i64) < Integer::from(0_u8));
+        assert!(Integer::from(i64::MAX) < Integer::from(u64::MAX));
+
+        assert_eq!(Integer::from(u64::MAX).to_i64(), None);
+        assert_eq!(Integer::from(-1_i32).to_u64(), None);
+        assert_eq!(Integer::from(-1_i32).to_i64(), Some(-1));
+    }
+
+    #[test]
+    fn from_value() {
+        assert_eq!(Integer::from_value(&json!(1.0)), Some(Integer::from(1_u8)));
+        assert_eq!(
+            Integer::from_value(&json!(-2.0)),
+            Some(Integer::from(-2_i8))
+        );
+        assert_eq!(Integer::from_value(&json!(1.5)), None);
+        assert_eq!(Integer::from_value(&json!(1e20)), None);
+        assert_eq!(Integer::from_value(&json!("1")), None);
+    }
+}
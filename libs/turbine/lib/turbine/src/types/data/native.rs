@@ -0,0 +1,216 @@
+use serde_json::Value;
+
+/// Data type, which is parsed into a native Rust type, instead of borrowing its JSON
+/// representation.
+pub(crate) trait Native: Copy {
+    fn from_value(value: &Value) -> Option<Self>;
+
+    /// Returns `None` if the value cannot be represented in JSON.
+    fn to_value(self) -> Option<Value>;
+}
+
+/// Storage of the mutable variant of a native data type.
+///
+/// The JSON value and the parsed value are kept in sync, as the mutable variant is either created
+/// from the underlying JSON value, or from the owned variant, which only stores the parsed value.
+#[derive(Debug)]
+pub(crate) enum Slot<'a, T> {
+    Value { raw: &'a mut Value, value: T },
+    Native(&'a mut T),
+}
+
+impl<'a, T> Slot<'a, T>
+where
+    T: Native,
+{
+    /// Returns the value back if it is not of the expected shape.
+    pub(crate) fn from_value(raw: &'a mut Value) -> Result<Self, &'a mut Value> {
+        match T::from_value(raw) {
+            Some(value) => Ok(Self::Value { raw, value }),
+            None => Err(raw),
+        }
+    }
+
+    pub(crate) const fn get(&self) -> T {
+        match self {
+            Self::Value { value, .. } => *value,
+            Self::Native(value) => **value,
+        }
+    }
+
+    /// Returns `false` if the value cannot be represented in JSON, the slot is left unchanged.
+    #[must_use]
+    pub(crate) fn set(&mut self, value: T) -> bool {
+        let Some(json) = value.to_value() else {
+            return false;
+        };
+
+        match self {
+            Self::Value {
+                raw,
+                value: current,
+            } => {
+                **raw = json;
+                *current = value;
+            }
+            Self::Native(current) => **current = value,
+        }
+
+        true
+    }
+}
+
+/// Implement the data type traits for a [`Native`] type `$name` and generate its mutable variant
+/// `$name_mut`, which stores the value in a [`Slot`].
+///
+/// Native types are `Copy`, the owned variant is therefore its own `Ref`. `$error::$variant` is
+/// reported for values, which cannot be parsed, and must take the offending value.
+///
+/// The type is a [`Refinement`] of the builtin `$parent` by the JSON schema constraint
+/// `$keyword: $constraint` and shares its URL.
+///
+/// [`Refinement`]: crate::types::data::Refinement
+macro_rules! native_data_type {
+    (
+        $name:ident,
+        $name_mut:ident,
+        $error:ident:: $variant:ident,
+        $parent:ident { $keyword:literal: $constraint:literal }
+    ) => {
+        impl serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                let value = <Self as $crate::types::data::native::Native>::to_value(*self)
+                    .ok_or_else(|| {
+                        <S::Error as serde::ser::Error>::custom(concat!(
+                            "`",
+                            stringify!($name),
+                            "` cannot be represented in JSON"
+                        ))
+                    })?;
+
+                serde::Serialize::serialize(&value, serializer)
+            }
+        }
+
+        impl $crate::TypeUrl for $name {
+            const ID: $crate::VersionedUrlRef<'static> =
+                <$crate::types::data::$parent as $crate::TypeUrl>::ID;
+        }
+
+        impl $crate::types::data::Refinement for $name {
+            type Parent = $crate::types::data::$parent;
+
+            const CONSTRAINT: (&'static str, &'static str) = ($keyword, $constraint);
+        }
+
+        impl $crate::TypeTraverse for $name {
+            type Path = $crate::types::data::DataTypePath;
+        }
+
+        impl $crate::Type for $name {
+            type Mut<'a> = $name_mut<'a> where Self: 'a;
+            type Ref<'a> = Self where Self: 'a;
+
+            fn as_mut(&mut self) -> Self::Mut<'_> {
+                $name_mut($crate::types::data::native::Slot::Native(self))
+            }
+
+            fn as_ref(&self) -> Self::Ref<'_> {
+                *self
+            }
+        }
+
+        impl $crate::DataType for $name {
+            type Error = $error;
+
+            fn try_from_value(value: serde_json::Value) -> error_stack::Result<Self, Self::Error> {
+                <Self as $crate::types::data::native::Native>::from_value(&value)
+                    .ok_or_else(|| error_stack::Report::new($error::$variant(value)))
+            }
+
+            fn is_valid_value(value: &serde_json::Value) -> bool {
+                <Self as $crate::types::data::native::Native>::from_value(value).is_some()
+            }
+        }
+
+        impl $crate::TypeRef for $name {
+            type Owned = Self;
+
+            fn into_owned(self) -> Self::Owned {
+                self
+            }
+        }
+
+        impl<'a> $crate::DataTypeRef<'a> for $name {
+            type Error = $error;
+
+            fn try_from_value(
+                value: &'a serde_json::Value,
+            ) -> error_stack::Result<Self, Self::Error> {
+                <Self as $crate::types::data::native::Native>::from_value(value)
+                    .ok_or_else(|| error_stack::Report::new($error::$variant(value.clone())))
+            }
+
+            fn try_from_raw(
+                value: &'a serde_json::value::RawValue,
+            ) -> error_stack::Result<Self, Self::Error> {
+                // scalar values are cheap to parse and not borrowed
+                <Self as $crate::DataTypeRef<'_>>::try_from_value(&$crate::raw::to_value_lossy(
+                    value,
+                ))
+            }
+        }
+
+        #[derive(Debug)]
+        pub struct $name_mut<'a>($crate::types::data::native::Slot<'a, $name>);
+
+        impl $name_mut<'_> {
+            #[must_use]
+            pub const fn get(&self) -> $name {
+                self.0.get()
+            }
+        }
+
+        impl serde::Serialize for $name_mut<'_> {
+            fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serde::Serialize::serialize(&self.get(), serializer)
+            }
+        }
+
+        impl $crate::TypeUrl for $name_mut<'_> {
+            const ID: $crate::VersionedUrlRef<'static> = <$name as $crate::TypeUrl>::ID;
+        }
+
+        impl $crate::TypeTraverse for $name_mut<'_> {
+            type Path = $crate::types::data::DataTypePath;
+        }
+
+        impl $crate::TypeMut for $name_mut<'_> {
+            type Owned = $name;
+
+            fn into_owned(self) -> Self::Owned {
+                self.get()
+            }
+        }
+
+        impl<'a> $crate::DataTypeMut<'a> for $name_mut<'a> {
+            type Error = $error;
+
+            fn try_from_value(
+                value: &'a mut serde_json::Value,
+            ) -> error_stack::Result<Self, Self::Error> {
+                $crate::types::data::native::Slot::from_value(value)
+                    .map(Self)
+                    .map_err(|value| error_stack::Report::new($error::$variant(value.clone())))
+            }
+        }
+    };
+}
+
+pub(crate) use native_data_type;
@@ -1,11 +1,11 @@
 use error_stack::{Report, Result};
 use onlyerror::Error;
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{value::RawValue, Value};
 
 use crate::{
-    types::data::DataTypePath, url, DataType, DataTypeMut, DataTypeRef, Type, TypeMut, TypeRef,
-    TypeTraverse, TypeUrl, VersionedUrlRef,
+    raw::to_value_lossy, types::data::DataTypePath, url, DataType, DataTypeMut, DataTypeRef, Type,
+    TypeMut, TypeRef, TypeTraverse, TypeUrl, VersionedUrlRef,
 };
 
 #[derive(Debug, Clone, Error)]
@@ -18,8 +18,6 @@ pub enum NullError {
 pub struct Null;
 
 impl TypeUrl for Null {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
         url!("https://blockprotocol.org/@blockprotocol/types/data-type/null/" / v / 1);
 }
@@ -76,6 +74,11 @@ impl<'a> DataTypeRef<'a> for Null {
             Err(Report::new(NullError::NotNull(value.clone())))
         }
     }
+
+    fn try_from_raw(value: &'a RawValue) -> Result<Self, Self::Error> {
+        // scalar values are cheap to parse and not borrowed
+        <Self as DataTypeRef<'_>>::try_from_value(&to_value_lossy(value))
+    }
 }
 
 impl TypeMut for Null {
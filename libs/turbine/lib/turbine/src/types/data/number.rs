@@ -1,13 +1,16 @@
+use alloc::borrow::Cow;
 use core::ops::{Deref, DerefMut};
 
 use error_stack::{Report, Result};
 use onlyerror::Error;
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{value::RawValue, Value};
 
 use crate::{
-    types::data::DataTypePath, url, DataType, DataTypeMut, DataTypeRef, Type, TypeMut, TypeRef,
-    TypeTraverse, TypeUrl, VersionedUrlRef,
+    raw::{self, to_value_lossy},
+    types::data::DataTypePath,
+    url, DataType, DataTypeMut, DataTypeRef, Type, TypeMut, TypeRef, TypeTraverse, TypeUrl,
+    VersionedUrlRef,
 };
 
 #[derive(Debug, Clone, Error)]
@@ -44,8 +47,6 @@ impl Deref for Number {
 }
 
 impl TypeUrl for Number {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
         url!("https://blockprotocol.org/@blockprotocol/types/data-type/number/" / v / 1);
 }
@@ -55,15 +56,21 @@ impl TypeTraverse for Number {
 }
 
 impl Type for Number {
-    type Mut<'a> = NumberMut<'a> where Self: 'a;
-    type Ref<'a> = NumberRef<'a> where Self: 'a;
+    type Mut<'a>
+        = NumberMut<'a>
+    where
+        Self: 'a;
+    type Ref<'a>
+        = NumberRef<'a>
+    where
+        Self: 'a;
 
     fn as_mut(&mut self) -> Self::Mut<'_> {
         NumberMut(&mut self.0)
     }
 
     fn as_ref(&self) -> Self::Ref<'_> {
-        NumberRef(&self.0)
+        NumberRef(Cow::Borrowed(&self.0))
     }
 }
 
@@ -83,20 +90,19 @@ impl DataType for Number {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize)]
-pub struct NumberRef<'a>(&'a serde_json::Number);
+/// Borrowed number, numbers read from raw JSON are parsed once and therefore owned.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct NumberRef<'a>(Cow<'a, serde_json::Number>);
 
 impl Deref for NumberRef<'_> {
     type Target = serde_json::Number;
 
     fn deref(&self) -> &Self::Target {
-        self.0
+        &self.0
     }
 }
 
 impl TypeUrl for NumberRef<'_> {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
         url!("https://blockprotocol.org/@blockprotocol/types/data-type/number/" / v / 1);
 }
@@ -109,7 +115,7 @@ impl TypeRef for NumberRef<'_> {
     type Owned = Number;
 
     fn into_owned(self) -> Self::Owned {
-        Number(self.0.clone())
+        Number(self.0.into_owned())
     }
 }
 
@@ -118,11 +124,18 @@ impl<'a> DataTypeRef<'a> for NumberRef<'a> {
 
     fn try_from_value(value: &'a Value) -> Result<Self, Self::Error> {
         if let Value::Number(value) = value {
-            Ok(Self(value))
+            Ok(Self(Cow::Borrowed(value)))
         } else {
             Err(Report::new(NumberError::NotANumber(value.clone())))
         }
     }
+
+    fn try_from_raw(value: &'a RawValue) -> Result<Self, Self::Error> {
+        raw::number(value).map_or_else(
+            || Err(Report::new(NumberError::NotANumber(to_value_lossy(value)))),
+            |value| Ok(Self(Cow::Owned(value))),
+        )
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -143,8 +156,6 @@ impl DerefMut for NumberMut<'_> {
 }
 
 impl TypeUrl for NumberMut<'_> {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
         url!("https://blockprotocol.org/@blockprotocol/types/data-type/number/" / v / 1);
 }
@@ -1,14 +1,14 @@
-use alloc::string::String;
+use alloc::{borrow::Cow, string::String};
 use core::ops::{Deref, DerefMut};
 
 use error_stack::{Report, Result};
 use onlyerror::Error;
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{value::RawValue, Value};
 
 use crate::{
-    types::data::DataTypePath, url, DataType, DataTypeMut, DataTypeRef, Type, TypeMut, TypeRef,
-    TypeTraverse, TypeUrl, VersionedUrlRef,
+    raw::to_value_lossy, types::data::DataTypePath, url, DataType, DataTypeMut, DataTypeRef, Type,
+    TypeMut, TypeRef, TypeTraverse, TypeUrl, VersionedUrlRef,
 };
 
 #[derive(Debug, Clone, Error)]
@@ -51,8 +51,6 @@ impl DerefMut for Object {
 }
 
 impl TypeUrl for Object {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
         url!("https://blockprotocol.org/@blockprotocol/types/data-type/object/" / v / 1);
 }
@@ -70,7 +68,7 @@ impl Type for Object {
     }
 
     fn as_ref(&self) -> Self::Ref<'_> {
-        ObjectRef(&self.0)
+        ObjectRef(Cow::Borrowed(&self.0))
     }
 }
 
@@ -90,20 +88,19 @@ impl DataType for Object {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
-pub struct ObjectRef<'a>(&'a serde_json::Map<String, Value>);
+/// Borrowed object, objects read from raw JSON are always parsed and therefore owned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ObjectRef<'a>(Cow<'a, serde_json::Map<String, Value>>);
 
 impl Deref for ObjectRef<'_> {
     type Target = serde_json::Map<String, Value>;
 
     fn deref(&self) -> &Self::Target {
-        self.0
+        &self.0
     }
 }
 
 impl TypeUrl for ObjectRef<'_> {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
         url!("https://blockprotocol.org/@blockprotocol/types/data-type/object/" / v / 1);
 }
@@ -116,18 +113,27 @@ impl TypeRef for ObjectRef<'_> {
     type Owned = Object;
 
     fn into_owned(self) -> Self::Owned {
-        Object(self.0.clone())
+        Object(self.0.into_owned())
     }
 }
+
 impl<'a> DataTypeRef<'a> for ObjectRef<'a> {
     type Error = ObjectError;
 
     fn try_from_value(value: &'a Value) -> Result<Self, Self::Error> {
         value.as_object().map_or_else(
             || Err(Report::new(ObjectError::NotAnObject(value.clone()))),
-            |value| Ok(Self(value)),
+            |value| Ok(Self(Cow::Borrowed(value))),
         )
     }
+
+    fn try_from_raw(value: &'a RawValue) -> Result<Self, Self::Error> {
+        serde_json::from_str(value.get())
+            .map(|value| Self(Cow::Owned(value)))
+            .map_err(|error| {
+                Report::new(ObjectError::NotAnObject(to_value_lossy(value))).attach_printable(error)
+            })
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -148,8 +154,6 @@ impl DerefMut for ObjectMut<'_> {
 }
 
 impl TypeUrl for ObjectMut<'_> {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
         url!("https://blockprotocol.org/@blockprotocol/types/data-type/object/" / v / 1);
 }
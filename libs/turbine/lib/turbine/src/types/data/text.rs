@@ -1,14 +1,19 @@
-use alloc::{borrow::ToOwned, string::String};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::String,
+};
 use core::ops::{Deref, DerefMut};
 
 use error_stack::{Report, Result};
 use onlyerror::Error;
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{value::RawValue, Value};
 
 use crate::{
-    types::data::DataTypePath, url, DataType, DataTypeMut, DataTypeRef, Type, TypeMut, TypeRef,
-    TypeTraverse, TypeUrl, VersionedUrlRef,
+    raw::{self, to_value_lossy},
+    types::data::DataTypePath,
+    url, DataType, DataTypeMut, DataTypeRef, Type, TypeMut, TypeRef, TypeTraverse, TypeUrl,
+    VersionedUrlRef,
 };
 
 #[derive(Debug, Clone, Error)]
@@ -51,10 +56,8 @@ impl DerefMut for Text {
 }
 
 impl TypeUrl for Text {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
-        url!("https://blockprotocol.org/@blockprotocol/types/data-type/object/" / v / 1);
+        url!("https://blockprotocol.org/@blockprotocol/types/data-type/text/" / v / 1);
 }
 
 impl TypeTraverse for Text {
@@ -62,15 +65,21 @@ impl TypeTraverse for Text {
 }
 
 impl Type for Text {
-    type Mut<'a> = TextMut<'a> where Self: 'a;
-    type Ref<'a> = TextRef<'a> where Self: 'a;
+    type Mut<'a>
+        = TextMut<'a>
+    where
+        Self: 'a;
+    type Ref<'a>
+        = TextRef<'a>
+    where
+        Self: 'a;
 
     fn as_mut(&mut self) -> Self::Mut<'_> {
         TextMut(&mut self.0)
     }
 
     fn as_ref(&self) -> Self::Ref<'_> {
-        TextRef(&self.0)
+        TextRef(Cow::Borrowed(&self.0))
     }
 }
 
@@ -90,13 +99,14 @@ impl DataType for Text {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
-pub struct TextRef<'a>(&'a str);
+/// Borrowed text, text read from raw JSON is only owned if it contains escape sequences.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct TextRef<'a>(Cow<'a, str>);
 
-impl<'a> TextRef<'a> {
+impl TextRef<'_> {
     #[must_use]
-    pub const fn as_str(&self) -> &'a str {
-        self.0
+    pub fn as_str(&self) -> &str {
+        &self.0
     }
 }
 
@@ -104,22 +114,20 @@ impl Deref for TextRef<'_> {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
-        self.0
+        &self.0
     }
 }
 
 impl TypeUrl for TextRef<'_> {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
-        url!("https://blockprotocol.org/@blockprotocol/types/data-type/object/" / v / 1);
+        url!("https://blockprotocol.org/@blockprotocol/types/data-type/text/" / v / 1);
 }
 
 impl TypeRef for TextRef<'_> {
     type Owned = Text;
 
     fn into_owned(self) -> Self::Owned {
-        Text(self.0.to_owned())
+        Text(self.0.into_owned())
     }
 }
 
@@ -133,6 +141,13 @@ impl<'a> DataTypeRef<'a> for TextRef<'a> {
     fn try_from_value(value: &'a Value) -> Result<Self, Self::Error> {
         value.as_str().map_or_else(
             || Err(Report::new(TextError::NotText(value.clone()))),
+            |value| Ok(Self(Cow::Borrowed(value))),
+        )
+    }
+
+    fn try_from_raw(value: &'a RawValue) -> Result<Self, Self::Error> {
+        raw::string(value).map_or_else(
+            || Err(Report::new(TextError::NotText(to_value_lossy(value)))),
             |value| Ok(Self(value)),
         )
     }
@@ -156,10 +171,8 @@ impl DerefMut for TextMut<'_> {
 }
 
 impl TypeUrl for TextMut<'_> {
-    type InheritsFrom = ();
-
     const ID: VersionedUrlRef<'static> =
-        url!("https://blockprotocol.org/@blockprotocol/types/data-type/object/" / v / 1);
+        url!("https://blockprotocol.org/@blockprotocol/types/data-type/text/" / v / 1);
 }
 
 impl TypeTraverse for TextMut<'_> {
@@ -0,0 +1,83 @@
+use error_stack::{Report, Result};
+use onlyerror::Error;
+use serde_json::Value;
+use time::UtcOffset;
+
+use crate::types::data::{
+    constraint::{format_time, parse_time},
+    native::{native_data_type, Native},
+};
+
+#[derive(Debug, Clone, Error)]
+pub enum TimeError {
+    #[error("`{0:?}` is not an RFC 3339 full-time")]
+    NotATime(Value),
+    #[error("`{0:?}` cannot be represented as an RFC 3339 full-time")]
+    Unrepresentable(Time),
+}
+
+/// Time of day, including the offset to UTC.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Time {
+    time: time::Time,
+    offset: UtcOffset,
+}
+
+impl Time {
+    #[must_use]
+    pub const fn new(time: time::Time, offset: UtcOffset) -> Self {
+        Self { time, offset }
+    }
+
+    #[must_use]
+    pub const fn time(self) -> time::Time {
+        self.time
+    }
+
+    #[must_use]
+    pub const fn offset(self) -> UtcOffset {
+        self.offset
+    }
+}
+
+impl From<time::Time> for Time {
+    fn from(value: time::Time) -> Self {
+        Self::new(value, UtcOffset::UTC)
+    }
+}
+
+impl Native for Time {
+    fn from_value(value: &Value) -> Option<Self> {
+        value
+            .as_str()
+            .and_then(parse_time)
+            .map(|(time, offset)| Self::new(time, offset))
+    }
+
+    fn to_value(self) -> Option<Value> {
+        format_time(self.time, self.offset).map(Value::String)
+    }
+}
+
+native_data_type!(
+    Time,
+    TimeMut,
+    TimeError::NotATime,
+    Text { "format": "time" }
+);
+
+impl TimeMut<'_> {
+    /// # Errors
+    ///
+    /// if the value cannot be represented as an RFC 3339 full-time, e.g. the year is not between
+    /// 0 and 9999, the current value is left unchanged.
+    pub fn set(&mut self, value: impl Into<Time>) -> Result<(), TimeError> {
+        let value = value.into();
+
+        if self.0.set(value) {
+            Ok(())
+        } else {
+            Err(Report::new(TimeError::Unrepresentable(value)))
+        }
+    }
+}
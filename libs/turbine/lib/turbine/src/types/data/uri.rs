@@ -0,0 +1,20 @@
+use onlyerror::Error;
+use serde_json::Value;
+
+use crate::types::data::{constraint::is_uri, format::format_data_type};
+
+#[derive(Debug, Clone, Error)]
+pub enum UriError {
+    #[error("`{0:?}` is not a URI")]
+    NotAUri(Value),
+}
+
+format_data_type!(
+    Uri,
+    UriRef,
+    UriMut,
+    UriError::NotAUri,
+    is_uri,
+    "a URI",
+    "uri"
+);